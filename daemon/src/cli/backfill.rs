@@ -0,0 +1,148 @@
+//! One-shot historical log backfill, run from `sqrl init` (unless
+//! `--skip-history`). Reuses the watcher's `LogParser`/`SessionTracker`/
+//! `PositionStore`: every existing Claude Code log file is parsed from
+//! position 0, fed through a fresh `SessionTracker`, force-closed into
+//! `CompletedSession`s, and submitted exactly like
+//! `watch::send_to_service`, then the `PositionStore` is seeded with each
+//! file's end offset so the live daemon won't re-ingest them.
+
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+use crate::cli::watch::send_to_service;
+use crate::cli::worker::{Worker, WorkerManager, WorkerState};
+use crate::error::Error;
+use crate::ipc::IpcClient;
+use crate::storage;
+use crate::watcher::{CompletedSession, FileWatcher, LogParser, PositionStore, SessionTracker};
+
+/// Discover every historical log file and hand them to a single
+/// [`BackfillWorker`] under its own [`WorkerManager`], so ingestion gets
+/// the same supervision as the live daemon's workers. `run` waits on
+/// that manager, so `sqrl init` blocks until the backfill has finished.
+pub async fn run(project_root: &Path) -> Result<(), Error> {
+    let files = discover_log_files()?;
+    if files.is_empty() {
+        println!("No historical logs found.");
+        return Ok(());
+    }
+
+    println!("Backfilling {} historical log file(s)...", files.len());
+
+    let mut manager = WorkerManager::new();
+    manager.spawn(Box::new(BackfillWorker {
+        project_root: project_root.to_path_buf(),
+        files,
+        index: 0,
+        episodes_sent: 0,
+        parser: LogParser::new(),
+        session_tracker: SessionTracker::new(),
+        position_store: PositionStore::new(PositionStore::default_path()?)?,
+        ipc_client: IpcClient::default(),
+    }));
+    manager.join().await;
+
+    println!("Historical log backfill complete.");
+    Ok(())
+}
+
+fn discover_log_files() -> Result<Vec<PathBuf>, Error> {
+    let claude_dir = FileWatcher::new()?.claude_dir().clone();
+    let mut files = Vec::new();
+    walk_jsonl(&claude_dir, &mut files)?;
+    Ok(files)
+}
+
+fn walk_jsonl(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk_jsonl(&path, out)?;
+        } else if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Processes one discovered file per tick and reports per-file progress
+/// through [`Worker::progress`]. It runs under `run`'s own
+/// `WorkerManager` inside `sqrl init`, not the daemon's, so `sqrl
+/// workers` does not list it.
+/// Finishes with `WorkerState::Done` once every file has been read and
+/// every session it contained has been force-closed and submitted.
+struct BackfillWorker {
+    project_root: PathBuf,
+    files: Vec<PathBuf>,
+    index: usize,
+    episodes_sent: usize,
+    parser: LogParser,
+    session_tracker: SessionTracker,
+    position_store: PositionStore,
+    ipc_client: IpcClient,
+}
+
+#[async_trait::async_trait]
+impl Worker for BackfillWorker {
+    fn name(&self) -> &str {
+        "history-backfill"
+    }
+
+    async fn work(&mut self) -> Result<WorkerState, Error> {
+        let Some(path) = self.files.get(self.index).cloned() else {
+            // Every file has been scanned; anything still open at this
+            // point has no more data coming, so close it out like an
+            // idle timeout would on the live daemon.
+            let completed = self.session_tracker.close_all_sessions();
+            for session in completed {
+                self.submit(session).await;
+            }
+            self.position_store.save()?;
+            return Ok(WorkerState::Done);
+        };
+
+        let (entries, end_pos) = self.parser.parse_from_position(&path, 0)?;
+        for entry in entries {
+            self.session_tracker.process_entry(entry);
+        }
+        self.position_store.set_position(path, end_pos)?;
+
+        self.index += 1;
+        Ok(WorkerState::Busy)
+    }
+
+    fn progress(&self) -> Option<String> {
+        Some(format!(
+            "{}/{} files scanned · {} episodes sent",
+            self.index.min(self.files.len()),
+            self.files.len(),
+            self.episodes_sent
+        ))
+    }
+}
+
+impl BackfillWorker {
+    /// Submit a completed session, skipping it if a memory already
+    /// mentioning its session id exists, so re-running `sqrl init`
+    /// doesn't double-submit history already ingested.
+    async fn submit(&mut self, session: CompletedSession) {
+        if session.events.is_empty() {
+            return;
+        }
+
+        match storage::get_memories_since(&self.project_root, 0) {
+            Ok(existing) if existing.iter().any(|m| m.content.contains(&session.session_id)) => {
+                return;
+            }
+            Err(e) => warn!(error = %e, "Failed to check existing memories for dedup"),
+            _ => {}
+        }
+
+        send_to_service(&self.ipc_client, session).await;
+        self.episodes_sent += 1;
+    }
+}
@@ -1,46 +1,87 @@
 //! Configure settings.
+//!
+//! Walks `Config`/`GlobalConfig` as a `serde_json::Value` tree instead of
+//! hand-written match arms over a fixed key set, so a new struct field is
+//! reachable from `sqrl config` the moment it's added — no second edit
+//! here to keep in sync. Type coercion falls out of that reflection for
+//! free, but per-field domain limits (see [`validate_leaf`]) don't, so
+//! those stay as an explicit, separately-maintained list.
+
+use serde_json::Value;
 
 use crate::config::Config;
 use crate::error::Error;
+use crate::global_config::GlobalConfig;
 
-/// Run config command.
+/// Run config command. Tries the project `Config` first, then falls back
+/// to `GlobalConfig` for keys the project config doesn't have (e.g.
+/// `tools.cursor`, `ui.port`) — the two layers can't collide since a
+/// `sqrl config KEY` lookup stops at whichever tree resolves `KEY` first.
 pub async fn run(key: Option<&str>, value: Option<&str>) -> Result<(), Error> {
-    let mut config = Config::load()?;
-
     match (key, value) {
-        // Show all config
+        // List every settable key across both layers.
         (None, None) => {
-            println!("Current configuration:");
-            println!();
-            println!("[agents]");
-            println!("  claude = {}", config.agents.claude);
-            println!("  cursor = {}", config.agents.cursor);
-            println!("  codex_cli = {}", config.agents.codex_cli);
-            println!("  gemini = {}", config.agents.gemini);
-            println!("  copilot = {}", config.agents.copilot);
-            println!("  windsurf = {}", config.agents.windsurf);
-            println!();
-            println!("[llm]");
-            println!("  strong_model = {}", config.llm.strong_model);
-            println!("  fast_model = {}", config.llm.fast_model);
-            println!("  embedding_model = {}", config.llm.embedding_model);
-            println!();
-            println!("[daemon]");
-            println!("  socket_path = {}", config.daemon.socket_path);
-            println!("  log_level = {}", config.daemon.log_level);
+            let config = Config::load()?;
+            println!("[project]");
+            for (path, leaf) in enumerate_leaves(&to_value(&config)?) {
+                println!("  {} = {}", path, leaf);
+            }
+
+            if GlobalConfig::exists() {
+                println!();
+                println!("[global]");
+                let global = GlobalConfig::load()?;
+                for (path, leaf) in enumerate_leaves(&to_value(&global)?) {
+                    println!("  {} = {}", path, leaf);
+                }
+            }
         }
 
-        // Show single key
+        // Show single key.
         (Some(key), None) => {
-            let value = get_config_value(&config, key)?;
-            println!("{} = {}", key, value);
+            let config = Config::load()?;
+            let tree = to_value(&config)?;
+            if let Some(leaf) = get_path(&tree, key) {
+                println!("{} = {}", key, leaf);
+                return Ok(());
+            }
+
+            if GlobalConfig::exists() {
+                let global = GlobalConfig::load()?;
+                if let Some(leaf) = get_path(&to_value(&global)?, key) {
+                    println!("{} = {}", key, leaf);
+                    return Ok(());
+                }
+            }
+
+            return Err(Error::InvalidConfig(format!("Unknown key: {}", key)));
         }
 
-        // Set key=value
+        // Set key=value.
         (Some(key), Some(value)) => {
-            set_config_value(&mut config, key, value)?;
-            config.save()?;
-            println!("Set {} = {}", key, value);
+            let mut config = Config::load()?;
+            let mut tree = to_value(&config)?;
+            if get_path(&tree, key).is_some() {
+                set_path(&mut tree, key, value)?;
+                config = serde_json::from_value(tree)?;
+                config.save()?;
+                println!("Set {} = {}", key, value);
+                return Ok(());
+            }
+
+            if GlobalConfig::exists() {
+                let mut global = GlobalConfig::load()?;
+                let mut global_tree = to_value(&global)?;
+                if get_path(&global_tree, key).is_some() {
+                    set_path(&mut global_tree, key, value)?;
+                    global = serde_json::from_value(global_tree)?;
+                    global.save()?;
+                    println!("Set {} = {}", key, value);
+                    return Ok(());
+                }
+            }
+
+            return Err(Error::InvalidConfig(format!("Unknown key: {}", key)));
         }
 
         _ => {
@@ -51,48 +92,118 @@ pub async fn run(key: Option<&str>, value: Option<&str>) -> Result<(), Error> {
     Ok(())
 }
 
-fn get_config_value(config: &Config, key: &str) -> Result<String, Error> {
-    match key {
-        "agents.claude" => Ok(config.agents.claude.to_string()),
-        "agents.cursor" => Ok(config.agents.cursor.to_string()),
-        "agents.codex_cli" => Ok(config.agents.codex_cli.to_string()),
-        "agents.gemini" => Ok(config.agents.gemini.to_string()),
-        "agents.copilot" => Ok(config.agents.copilot.to_string()),
-        "agents.windsurf" => Ok(config.agents.windsurf.to_string()),
-        "llm.strong_model" => Ok(config.llm.strong_model.clone()),
-        "llm.fast_model" => Ok(config.llm.fast_model.clone()),
-        "llm.embedding_model" => Ok(config.llm.embedding_model.clone()),
-        "daemon.socket_path" => Ok(config.daemon.socket_path.clone()),
-        "daemon.log_level" => Ok(config.daemon.log_level.clone()),
-        _ => Err(Error::InvalidConfig(format!("Unknown key: {}", key))),
+fn to_value<T: serde::Serialize>(v: &T) -> Result<Value, Error> {
+    Ok(serde_json::to_value(v)?)
+}
+
+/// Resolve a dotted key path (`ui.port`, `tools.cursor`) by descending
+/// `value` one object level per segment; returns `None` if any segment
+/// is missing or not an object, or the final value is itself an object
+/// (only leaves are settable keys).
+fn get_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = current.as_object()?.get(segment)?;
+    }
+    if current.is_object() {
+        None
+    } else {
+        Some(current)
+    }
+}
+
+/// Parse `raw` into the same JSON type as the existing leaf at `path`
+/// (bool/number/string), then write it back. Validated by the caller
+/// re-deserializing the whole tree into the typed struct afterwards.
+fn set_path(value: &mut Value, path: &str, raw: &str) -> Result<(), Error> {
+    let segments: Vec<&str> = path.split('.').collect();
+    let (last, parents) = segments.split_last().expect("path has at least one segment");
+
+    let mut current = value;
+    for segment in parents {
+        current = current
+            .as_object_mut()
+            .and_then(|obj| obj.get_mut(*segment))
+            .ok_or_else(|| Error::InvalidConfig(format!("Unknown key: {}", path)))?;
     }
+
+    let obj = current
+        .as_object_mut()
+        .ok_or_else(|| Error::InvalidConfig(format!("Unknown key: {}", path)))?;
+    let existing = obj
+        .get(*last)
+        .ok_or_else(|| Error::InvalidConfig(format!("Unknown key: {}", path)))?;
+
+    let parsed = parse_leaf(existing, raw)?;
+    validate_leaf(path, &parsed)?;
+    obj.insert(last.to_string(), parsed);
+    Ok(())
 }
 
-fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<(), Error> {
-    match key {
-        "agents.claude" => config.agents.claude = parse_bool(value)?,
-        "agents.cursor" => config.agents.cursor = parse_bool(value)?,
-        "agents.codex_cli" => config.agents.codex_cli = parse_bool(value)?,
-        "agents.gemini" => config.agents.gemini = parse_bool(value)?,
-        "agents.copilot" => config.agents.copilot = parse_bool(value)?,
-        "agents.windsurf" => config.agents.windsurf = parse_bool(value)?,
-        "llm.strong_model" => config.llm.strong_model = value.to_string(),
-        "llm.fast_model" => config.llm.fast_model = value.to_string(),
-        "llm.embedding_model" => config.llm.embedding_model = value.to_string(),
-        "daemon.socket_path" => config.daemon.socket_path = value.to_string(),
-        "daemon.log_level" => config.daemon.log_level = value.to_string(),
-        _ => return Err(Error::InvalidConfig(format!("Unknown key: {}", key))),
+/// Per-key bounds that don't follow from JSON type alone. Reflecting over
+/// `Config`/`GlobalConfig` gets us type coercion for free, but domain
+/// limits (a tranquility knob that must stay `0..=10`, say) live here
+/// instead, keyed by the same dotted path `sqrl config` takes on the CLI.
+fn validate_leaf(path: &str, value: &Value) -> Result<(), Error> {
+    if path == "daemon.scrub_tranquility" {
+        let n = value
+            .as_i64()
+            .ok_or_else(|| Error::InvalidConfig(format!("{} must be a number", path)))?;
+        if !(0..=10).contains(&n) {
+            return Err(Error::InvalidConfig(format!(
+                "{} must be between 0 and 10",
+                path
+            )));
+        }
     }
     Ok(())
 }
 
-fn parse_bool(value: &str) -> Result<bool, Error> {
-    match value.to_lowercase().as_str() {
-        "true" | "1" | "yes" | "on" => Ok(true),
-        "false" | "0" | "no" | "off" => Ok(false),
-        _ => Err(Error::InvalidConfig(format!(
-            "Invalid boolean value: {}",
-            value
-        ))),
+/// Parse `raw` into whichever JSON scalar type `existing` already is.
+fn parse_leaf(existing: &Value, raw: &str) -> Result<Value, Error> {
+    match existing {
+        Value::Bool(_) => match raw.to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(Value::Bool(true)),
+            "false" | "0" | "no" | "off" => Ok(Value::Bool(false)),
+            _ => Err(Error::InvalidConfig(format!("Invalid boolean value: {}", raw))),
+        },
+        Value::Number(_) => raw
+            .parse::<i64>()
+            .map(|n| Value::Number(n.into()))
+            .or_else(|_| {
+                raw.parse::<f64>()
+                    .map_err(|_| Error::InvalidConfig(format!("Invalid number value: {}", raw)))
+                    .and_then(|f| {
+                        serde_json::Number::from_f64(f)
+                            .map(Value::Number)
+                            .ok_or_else(|| Error::InvalidConfig(format!("Invalid number value: {}", raw)))
+                    })
+            }),
+        _ => Ok(Value::String(raw.to_string())),
+    }
+}
+
+/// Every dotted leaf path in `value`, in object-key order, for `sqrl
+/// config` with no args to enumerate without hand-maintaining a list.
+fn enumerate_leaves(value: &Value) -> Vec<(String, Value)> {
+    let mut out = Vec::new();
+    collect_leaves(value, String::new(), &mut out);
+    out
+}
+
+fn collect_leaves(value: &Value, prefix: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                collect_leaves(child, path, out);
+            }
+        }
+        Value::Array(_) => out.push((prefix, value.clone())),
+        leaf => out.push((prefix, leaf.clone())),
     }
 }
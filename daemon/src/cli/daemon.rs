@@ -1,5 +1,10 @@
 //! Background daemon.
 
+use std::collections::HashSet;
+use std::path::Path;
+
+use tokio::signal::unix::{signal, SignalKind};
+
 use crate::config::{Config, ProjectsRegistry};
 use crate::error::Error;
 use crate::watcher::LogWatcher;
@@ -11,7 +16,7 @@ pub async fn run() -> Result<(), Error> {
     tracing::info!("Socket: {}", config.daemon.socket_path);
 
     // Load registered projects
-    let registry = ProjectsRegistry::load()?;
+    let mut registry = ProjectsRegistry::load()?;
     tracing::info!("Watching {} projects", registry.projects.len());
 
     // Create watcher
@@ -19,38 +24,104 @@ pub async fn run() -> Result<(), Error> {
 
     // Add project paths to watch
     for project in &registry.projects {
-        if let Err(e) = watcher.watch_project(&project.root_path) {
+        if let Err(e) = watcher.add(&project.project_id, &project.root_path) {
             tracing::warn!("Failed to watch {}: {}", project.root_path.display(), e);
         }
     }
 
     // Start IPC server
     let socket_path = config.daemon.socket_path.clone();
-    let ipc_handle = tokio::spawn(async move {
-        if let Err(e) = crate::ipc::run_server(&socket_path).await {
+    let ipc_socket_path = socket_path.clone();
+    let mut ipc_handle = tokio::spawn(async move {
+        if let Err(e) = crate::ipc::run_server(&ipc_socket_path).await {
             tracing::error!("IPC server error: {}", e);
         }
     });
 
-    // Run watcher loop
-    let watcher_handle = tokio::spawn(async move {
-        watcher.run().await;
-    });
+    // Following watchexec's signal module: SIGHUP reloads the project
+    // registry in place, SIGTERM/SIGINT trigger an ordered shutdown.
+    let mut sighup = signal(SignalKind::hangup())?;
+    let mut sigterm = signal(SignalKind::terminate())?;
 
-    tracing::info!("Daemon running. Press Ctrl+C to stop.");
+    tracing::info!("Daemon running. Press Ctrl+C to stop, SIGHUP to reload projects.");
 
-    // Wait for shutdown signal
-    tokio::select! {
-        _ = tokio::signal::ctrl_c() => {
-            tracing::info!("Shutting down...");
+    loop {
+        tokio::select! {
+            _ = sighup.recv() => {
+                tracing::info!("SIGHUP received, reloading project registry...");
+                reload_projects(&mut watcher, &mut registry);
+            }
+            _ = sigterm.recv() => {
+                tracing::info!("SIGTERM received, shutting down...");
+                break;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Ctrl+C received, shutting down...");
+                break;
+            }
+            res = &mut ipc_handle => {
+                if let Err(e) = res {
+                    tracing::error!("IPC server task panicked: {}", e);
+                }
+                tracing::error!("IPC server stopped unexpectedly");
+                break;
+            }
+            continued = watcher.tick() => {
+                if !continued {
+                    tracing::error!("Watcher channel closed unexpectedly");
+                    break;
+                }
+            }
         }
-        _ = ipc_handle => {
-            tracing::error!("IPC server stopped unexpectedly");
-        }
-        _ = watcher_handle => {
-            tracing::error!("Watcher stopped unexpectedly");
+    }
+
+    // Ordered shutdown: unlike `FileWatcher`, `LogWatcher` has no
+    // debounce stage to flush; `tick` handles one event at a time, and
+    // events still queued in its channel at this point are dropped. Abort the IPC task and
+    // remove the socket file so a later `sqrl daemon` doesn't find a
+    // stale one.
+    ipc_handle.abort();
+    if Path::new(&socket_path).exists() {
+        if let Err(e) = std::fs::remove_file(&socket_path) {
+            tracing::warn!("Failed to remove socket file: {}", e);
         }
     }
 
     Ok(())
 }
+
+/// Diff a freshly reloaded `ProjectsRegistry` against the watcher's
+/// current set, adding newly-registered projects and unwatching ones
+/// that were removed, without dropping the IPC server or restarting.
+fn reload_projects(watcher: &mut LogWatcher, registry: &mut ProjectsRegistry) {
+    let fresh = match ProjectsRegistry::load() {
+        Ok(fresh) => fresh,
+        Err(e) => {
+            tracing::warn!("Failed to reload project registry: {}", e);
+            return;
+        }
+    };
+
+    let previous_ids: HashSet<String> = watcher.watched_project_ids();
+    let current_ids: HashSet<String> = fresh.projects.iter().map(|p| p.project_id.clone()).collect();
+
+    for project in &fresh.projects {
+        if !previous_ids.contains(&project.project_id) {
+            match watcher.add(&project.project_id, &project.root_path) {
+                Ok(()) => tracing::info!("Now watching newly-registered project: {}", project.project_id),
+                Err(e) => tracing::warn!("Failed to watch {}: {}", project.root_path.display(), e),
+            }
+        }
+    }
+
+    for project in &registry.projects {
+        if !current_ids.contains(&project.project_id) {
+            match watcher.remove(&project.project_id) {
+                Ok(()) => tracing::info!("Stopped watching removed project: {}", project.project_id),
+                Err(e) => tracing::warn!("Failed to unwatch {}: {}", project.root_path.display(), e),
+            }
+        }
+    }
+
+    *registry = fresh;
+}
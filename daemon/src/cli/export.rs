@@ -1,71 +1,64 @@
-//! Export memories as JSON.
+//! Export memories as newline-delimited JSON, importable via `import`
+//! (which also still reads the older whole-document schema this format
+//! replaces).
+//!
+//! One line per memory keeps the output streamable in both directions:
+//! `import` never has to hold the whole file in memory, and `export`
+//! writes each row as soon as it's serialized instead of building one
+//! giant JSON value. Round-tripping through `export`/`import` re-inserts
+//! every row through `storage::store_memory`'s existing dedup path, so
+//! nothing needs to be deduplicated on the way out.
 
-use crate::config::Config;
-use crate::db::Database;
-use crate::error::Error;
-
-/// Run export command.
-pub async fn run(kind: Option<&str>, project: bool) -> Result<(), Error> {
-    let db_path = if project {
-        let cwd = std::env::current_dir()?;
-        cwd.join(".sqrl").join("squirrel.db")
-    } else {
-        Config::global_db_path()
-    };
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-    if !db_path.exists() {
-        println!("No database found at {}", db_path.display());
-        return Ok(());
-    }
+use flate2::write::GzEncoder;
+use flate2::Compression;
 
-    let db = Database::open(&db_path)?;
+use crate::error::Error;
+use crate::storage;
 
-    // Build query
-    let mut sql = String::from(
-        "SELECT id, project_id, scope, owner_type, owner_id, kind, tier, \
-         polarity, key, text, status, confidence, expires_at, created_at, updated_at \
-         FROM memories WHERE status != 'deprecated'",
-    );
+/// Run export command. Writes to `path`, or stdout if `path` is `None`.
+/// Compression is chosen by `path`'s extension (`.gz`/`.zst`/`.br`); when
+/// writing to stdout (no extension to go by), `gzip` controls whether the
+/// stream is gzip-compressed.
+pub fn run(
+    project_root: &Path,
+    memory_type: Option<&str>,
+    path: Option<&PathBuf>,
+    gzip: bool,
+) -> Result<(), Error> {
+    let memories = storage::get_memories(project_root, memory_type, None, None)?;
 
-    if let Some(k) = kind {
-        sql.push_str(&format!(" AND kind = '{}'", k));
+    let mut writer = open_compressed(path, gzip)?;
+    for memory in &memories {
+        serde_json::to_writer(&mut writer, memory)?;
+        writer.write_all(b"\n")?;
     }
+    writer.flush()?;
 
-    sql.push_str(" ORDER BY created_at DESC");
-
-    let mut stmt = db.conn().prepare(&sql)?;
-    let mut rows = stmt.query([])?;
+    Ok(())
+}
 
-    let mut memories: Vec<serde_json::Value> = Vec::new();
+/// Open `path` for writing (or stdout if `None`), transparently applying
+/// `.gz`/`.zst`/`.br` compression by extension. Falls back to the
+/// explicit `gzip` flag when writing to stdout, where there's no
+/// filename to detect a format from.
+fn open_compressed(path: Option<&PathBuf>, gzip: bool) -> Result<Box<dyn Write>, Error> {
+    let Some(path) = path else {
+        let stdout = std::io::stdout();
+        return if gzip {
+            Ok(Box::new(GzEncoder::new(stdout, Compression::default())))
+        } else {
+            Ok(Box::new(stdout))
+        };
+    };
 
-    while let Some(row) = rows.next()? {
-        let memory = serde_json::json!({
-            "id": row.get::<_, String>("id")?,
-            "project_id": row.get::<_, Option<String>>("project_id")?,
-            "scope": row.get::<_, String>("scope")?,
-            "owner_type": row.get::<_, String>("owner_type")?,
-            "owner_id": row.get::<_, String>("owner_id")?,
-            "kind": row.get::<_, String>("kind")?,
-            "tier": row.get::<_, String>("tier")?,
-            "polarity": row.get::<_, i32>("polarity")?,
-            "key": row.get::<_, Option<String>>("key")?,
-            "text": row.get::<_, String>("text")?,
-            "status": row.get::<_, String>("status")?,
-            "confidence": row.get::<_, Option<f64>>("confidence")?,
-            "expires_at": row.get::<_, Option<String>>("expires_at")?,
-            "created_at": row.get::<_, String>("created_at")?,
-            "updated_at": row.get::<_, String>("updated_at")?,
-        });
-        memories.push(memory);
+    let file = std::fs::File::create(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("gz") => Ok(Box::new(GzEncoder::new(file, Compression::default()))),
+        Some("zst") => Ok(Box::new(zstd::stream::write::Encoder::new(file, 0)?.auto_finish())),
+        Some("br") => Ok(Box::new(brotli::CompressorWriter::new(file, 4096, 5, 22))),
+        _ => Ok(Box::new(file)),
     }
-
-    let output = serde_json::json!({
-        "version": "1.0",
-        "exported_at": chrono::Utc::now().to_rfc3339(),
-        "memories": memories,
-    });
-
-    println!("{}", serde_json::to_string_pretty(&output)?);
-
-    Ok(())
 }
@@ -1,5 +1,6 @@
 //! Soft delete (deprecate) memories.
 
+use crate::cli::search::lexical_search;
 use crate::config::Config;
 use crate::db::Database;
 use crate::error::Error;
@@ -17,18 +18,13 @@ pub async fn run(id: Option<&str>, query: Option<&str>, confirm: bool) -> Result
         // Direct ID lookup
         id.to_string()
     } else if let Some(q) = query {
-        // Search for memory
-        let mut stmt = db.conn().prepare(
-            "SELECT id, text FROM memories WHERE text LIKE ? AND status != 'deprecated' LIMIT 1",
-        )?;
-        let pattern = format!("%{}%", q);
-        let mut rows = stmt.query([&pattern])?;
-
-        if let Some(row) = rows.next()? {
-            let id: String = row.get("id")?;
-            let text: String = row.get("text")?;
-            println!("Found memory: {}", text);
-            id
+        // Search for memory via the same ranked FTS5 path as `cli::search`,
+        // instead of a crude `text LIKE` scan.
+        let hits = lexical_search(&db, q, None, None)?;
+
+        if let Some(hit) = hits.into_iter().next() {
+            println!("Found memory: {}", hit.text);
+            hit.id
         } else {
             println!("No memory found matching '{}'", q);
             return Ok(());
@@ -1,45 +1,107 @@
 //! Git hook installation and management.
+//!
+//! Hooks are rendered from Tera templates instead of hand-edited shell
+//! strings, so adding a new hook type or changing what it invokes doesn't
+//! require touching the substring-matching logic that installs/removes it.
 
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
+use tera::{Context, Tera};
 use tracing::info;
 
+use crate::config::HooksConfig;
 use crate::error::Error;
 
-/// Pre-push hook script content.
-/// Shows diff summary for AI to review before push.
-const PRE_PUSH_HOOK: &str = r#"#!/bin/sh
-# Squirrel: shows changes for doc review before push
-# AI reads this output and decides if docs need updating
+/// Git hook types Squirrel knows how to render and manage.
+pub const HOOK_TYPES: &[&str] = &["pre-commit", "commit-msg", "post-commit", "pre-push"];
+
+/// Marks the start/end of the block Squirrel owns inside a hook script, so
+/// installing/uninstalling never touches a line a user wrote themselves.
+const BLOCK_START: &str = "# >>> squirrel hook block >>>";
+const BLOCK_END: &str = "# <<< squirrel hook block <<<";
+
+/// Tera template rendered for a given hook type. `{{ internal_cmd }}` is the
+/// `sqrl _internal` subcommand the hook invokes; hooks with no subcommand
+/// (yet) just exit 0 so they can still be extended later without reinstalling.
+fn template_for(hook: &str) -> &'static str {
+    match hook {
+        "pre-push" => {
+            "#!/bin/sh\n\
+             {{ block_start }}\n\
+             # Squirrel: shows changes for doc review before push\n\
+             # AI reads this output and decides if docs need updating\n\
+             sqrl _internal {{ internal_cmd }} 2>/dev/null || true\n\
+             {{ block_end }}\n"
+        }
+        "post-commit" => {
+            "#!/bin/sh\n\
+             {{ block_start }}\n\
+             # Squirrel: records doc debt for this commit\n\
+             sqrl _internal {{ internal_cmd }} 2>/dev/null || true\n\
+             {{ block_end }}\n"
+        }
+        "commit-msg" | "pre-commit" => {
+            "#!/bin/sh\n\
+             {{ block_start }}\n\
+             # Squirrel: {{ internal_cmd }}\n\
+             exit 0\n\
+             {{ block_end }}\n"
+        }
+        _ => unreachable!("unknown hook type {hook}"),
+    }
+}
+
+/// Maps a hook type to the `sqrl _internal` subcommand it invokes.
+/// Hooks without a wired subcommand yet render as a documented no-op.
+fn internal_cmd_for(hook: &str) -> &'static str {
+    match hook {
+        "pre-push" => "docguard-check",
+        "post-commit" => "docguard-record",
+        _ => "not yet configured",
+    }
+}
 
-sqrl _internal docguard-check 2>/dev/null || true
-"#;
+/// Render a hook script from its template.
+fn render_hook(hook: &str, project_root: &Path) -> Result<String, Error> {
+    let mut ctx = Context::new();
+    ctx.insert("block_start", BLOCK_START);
+    ctx.insert("block_end", BLOCK_END);
+    ctx.insert("internal_cmd", internal_cmd_for(hook));
+    ctx.insert("project_root", &project_root.display().to_string());
+
+    Tera::one_off(template_for(hook), &ctx, false)
+        .map_err(|e| Error::Hooks(format!("failed to render {hook} hook: {e}")))
+}
 
 /// Check if git is initialized in the project.
 pub fn has_git(project_root: &Path) -> bool {
     project_root.join(".git").exists()
 }
 
+/// Check if a given hook type is already installed (has a Squirrel block).
+pub fn hook_installed(project_root: &Path, hook: &str) -> bool {
+    let path = project_root.join(".git").join("hooks").join(hook);
+    fs::read_to_string(&path)
+        .map(|content| content.contains(BLOCK_START))
+        .unwrap_or(false)
+}
+
 /// Check if Squirrel hooks are already installed.
 #[allow(dead_code)]
 pub fn hooks_installed(project_root: &Path) -> bool {
-    let hooks_dir = project_root.join(".git").join("hooks");
-    let pre_push = hooks_dir.join("pre-push");
-
-    if !pre_push.exists() {
-        return false;
-    }
-
-    // Check if it's our hook (contains "Squirrel")
-    fs::read_to_string(&pre_push)
-        .map(|content| content.contains("Squirrel"))
-        .unwrap_or(false)
+    hook_installed(project_root, "pre-push")
 }
 
-/// Install Squirrel git hooks.
-pub fn install_hooks(project_root: &Path, _pre_push_block: bool) -> Result<(), Error> {
+/// Install the hooks enabled in `config`. With `overwrite`, a hook whose
+/// block already exists is re-rendered in place instead of being skipped;
+/// either way, hand-written content outside the Squirrel block is preserved.
+pub fn install_hooks(
+    project_root: &Path,
+    config: &HooksConfig,
+    overwrite: bool,
+) -> Result<(), Error> {
     let git_dir = project_root.join(".git");
     if !git_dir.exists() {
         return Ok(()); // No git, nothing to do
@@ -48,28 +110,41 @@ pub fn install_hooks(project_root: &Path, _pre_push_block: bool) -> Result<(), E
     let hooks_dir = git_dir.join("hooks");
     fs::create_dir_all(&hooks_dir)?;
 
-    // Install pre-push hook only
-    let pre_push_path = hooks_dir.join("pre-push");
-    install_hook(&pre_push_path, PRE_PUSH_HOOK)?;
-    info!("Installed pre-push hook");
+    for hook in HOOK_TYPES {
+        if !config.enabled.iter().any(|h| h == hook) {
+            continue;
+        }
+
+        let rendered = render_hook(hook, project_root)?;
+        let path = hooks_dir.join(hook);
+        install_hook(&path, &rendered, overwrite)?;
+        info!(hook = %hook, "Installed hook");
+    }
 
     Ok(())
 }
 
-/// Install a single hook, preserving existing hooks.
-fn install_hook(path: &Path, content: &str) -> Result<(), Error> {
+/// Install a single rendered hook, preserving any existing non-Squirrel
+/// content. If a Squirrel block already exists, it's left untouched unless
+/// `overwrite` is set, in which case it's replaced in place.
+fn install_hook(path: &Path, rendered: &str, overwrite: bool) -> Result<(), Error> {
     let final_content = if path.exists() {
         let existing = fs::read_to_string(path)?;
+        // An existing script keeps its own shebang; only the marked block
+        // of the rendered script goes into it.
+        let rendered_block = extract_block(rendered).unwrap_or_else(|| rendered.trim_end().to_string());
 
-        // Already has our hook
-        if existing.contains("Squirrel") {
-            return Ok(());
+        if let Some(block) = extract_block(&existing) {
+            if !overwrite {
+                return Ok(());
+            }
+            existing.replace(&block, &rendered_block)
+        } else {
+            // No Squirrel block yet: append ours to the user's existing hook.
+            format!("{}\n\n{}\n", existing.trim(), rendered_block)
         }
-
-        // Append to existing hook
-        format!("{}\n\n{}", existing.trim(), content)
     } else {
-        content.to_string()
+        rendered.to_string()
     };
 
     fs::write(path, &final_content)?;
@@ -82,63 +157,52 @@ fn install_hook(path: &Path, content: &str) -> Result<(), Error> {
     Ok(())
 }
 
-/// Uninstall Squirrel git hooks.
+/// Extract the Squirrel-owned block (including markers) from hook content.
+fn extract_block(content: &str) -> Option<String> {
+    let start = content.find(BLOCK_START)?;
+    let end = content.find(BLOCK_END)? + BLOCK_END.len();
+    Some(content[start..end].to_string())
+}
+
+/// Uninstall Squirrel git hooks, removing only the blocks it rendered.
 pub fn uninstall_hooks(project_root: &Path) -> Result<(), Error> {
     let hooks_dir = project_root.join(".git").join("hooks");
     if !hooks_dir.exists() {
         return Ok(());
     }
 
-    // Only pre-push now
-    let hook_path = hooks_dir.join("pre-push");
-    if hook_path.exists() {
-        let content = fs::read_to_string(&hook_path)?;
-        if content.contains("Squirrel") {
-            // Remove our section or the entire file
-            let cleaned = remove_squirrel_section(&content);
-            // Check if only shebangs and whitespace remain
-            let meaningful_content = cleaned
-                .lines()
-                .filter(|line| !line.trim().is_empty() && !line.starts_with("#!"))
-                .count();
-            if meaningful_content == 0 {
-                fs::remove_file(&hook_path)?;
-            } else {
-                fs::write(&hook_path, cleaned)?;
-            }
-            info!("Removed Squirrel pre-push hook");
+    for hook in HOOK_TYPES {
+        let path = hooks_dir.join(hook);
+        if !path.exists() {
+            continue;
         }
-    }
 
-    // Also clean up old post-commit hook if it exists
-    let post_commit_path = hooks_dir.join("post-commit");
-    if post_commit_path.exists() {
-        let content = fs::read_to_string(&post_commit_path)?;
-        if content.contains("Squirrel") {
-            let cleaned = remove_squirrel_section(&content);
-            let meaningful_content = cleaned
-                .lines()
-                .filter(|line| !line.trim().is_empty() && !line.starts_with("#!"))
-                .count();
-            if meaningful_content == 0 {
-                fs::remove_file(&post_commit_path)?;
-            } else {
-                fs::write(&post_commit_path, cleaned)?;
-            }
-            info!("Removed old Squirrel post-commit hook");
+        let content = fs::read_to_string(&path)?;
+        if extract_block(&content).is_none() {
+            continue;
         }
+
+        let cleaned = remove_squirrel_block(&content);
+        let meaningful_content = cleaned
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with("#!"))
+            .count();
+
+        if meaningful_content == 0 {
+            fs::remove_file(&path)?;
+        } else {
+            fs::write(&path, cleaned)?;
+        }
+        info!(hook = %hook, "Removed Squirrel hook block");
     }
 
     Ok(())
 }
 
-/// Remove Squirrel section from hook content.
-fn remove_squirrel_section(content: &str) -> String {
-    content
-        .lines()
-        .filter(|line| {
-            !line.contains("Squirrel") && !line.contains("sqrl _internal") && !line.contains("doc")
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
+/// Remove the Squirrel block (and surrounding blank lines) from hook content.
+fn remove_squirrel_block(content: &str) -> String {
+    match extract_block(content) {
+        Some(block) => content.replace(&block, "").trim().to_string(),
+        None => content.to_string(),
+    }
 }
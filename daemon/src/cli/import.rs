@@ -1,72 +1,212 @@
-//! Import memories from JSON.
+//! Import memories, streaming newline-delimited JSON (one memory object
+//! per line) through a `BufReader` so large exports don't need to fit in
+//! memory at once, plus the original whole-document `{"version",
+//! "exported_at","memories":[...]}` schema for backward compatibility.
+//!
+//! Both are re-inserted through `storage::store_memory`'s dedup path, so
+//! re-importing the same bank reinforces `use_count` instead of creating
+//! duplicates.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use serde::Deserialize;
 
-use crate::config::Config;
-use crate::db::{Database, Memory};
 use crate::error::Error;
+use crate::storage;
+
+/// Export schema version the whole-document path understands.
+const SUPPORTED_VERSION: &str = "1.0";
 
-/// Run import command.
-pub async fn run(file: &str) -> Result<(), Error> {
-    let content = std::fs::read_to_string(file)?;
-    let data: serde_json::Value = serde_json::from_str(&content)?;
+/// gzip member header (RFC 1952).
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
-    let memories = data["memories"]
-        .as_array()
-        .ok_or_else(|| Error::other("Invalid JSON: missing 'memories' array"))?;
+/// zstd frame magic number (RFC 8878).
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
 
-    let db_path = Config::global_db_path();
-    if let Some(parent) = db_path.parent() {
-        std::fs::create_dir_all(parent)?;
+/// One memory as exchanged in NDJSON form — one of these per line.
+#[derive(Debug, Deserialize)]
+struct ExportedMemory {
+    #[serde(default)]
+    memory_type: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportDocument {
+    version: String,
+    memories: Vec<ExportedMemory>,
+}
+
+/// Run import command. Reads from `path`, or stdin if `path` is `None`.
+/// With `replace`, every existing memory in the project is cleared
+/// before importing (instead of merging into what's already there).
+pub fn run(project_root: &Path, path: Option<&PathBuf>, replace: bool) -> Result<(), Error> {
+    let is_ndjson = path.map(is_ndjson_path).unwrap_or(false);
+    let reader = BufReader::new(open_decompressed(path)?);
+
+    if replace {
+        storage::clear_memories(project_root)?;
+        println!("Cleared existing memories before import (--replace).");
     }
-    let db = Database::open(&db_path)?;
 
+    let (imported, reinforced, skipped) = if is_ndjson {
+        import_ndjson(project_root, reader)?
+    } else {
+        import_document(project_root, reader)?
+    };
+
+    println!(
+        "Import complete: {} new, {} reinforced, {} skipped.",
+        imported, reinforced, skipped
+    );
+
+    Ok(())
+}
+
+/// Whether `path` names an NDJSON export (`.ndjson`/`.jsonl`, with an
+/// optional `.gz`/`.zst`/`.br` compression suffix stripped first).
+/// Stdin (no path) and any other extension go through
+/// [`import_document`], which also accepts NDJSON input.
+fn is_ndjson_path(path: &PathBuf) -> bool {
+    let stem = match path.extension().and_then(|e| e.to_str()) {
+        Some("gz") | Some("zst") | Some("br") => path.with_extension(""),
+        _ => path.clone(),
+    };
+    matches!(
+        stem.extension().and_then(|e| e.to_str()),
+        Some("ndjson") | Some("jsonl")
+    )
+}
+
+/// Stream NDJSON: one memory object per line, skipping blank lines and
+/// lines that fail to parse instead of aborting the whole import.
+fn import_ndjson(
+    project_root: &Path,
+    reader: BufReader<Box<dyn Read>>,
+) -> Result<(i32, i32, i32), Error> {
     let mut imported = 0;
+    let mut reinforced = 0;
     let mut skipped = 0;
 
-    for m in memories {
-        let id = m["id"].as_str().ok_or_else(|| Error::other("Missing id"))?;
-
-        // Skip if already exists
-        if db.get_memory(id)?.is_some() {
-            skipped += 1;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
             continue;
         }
 
-        let memory = Memory {
-            id: id.to_string(),
-            project_id: m["project_id"].as_str().map(|s| s.to_string()),
-            scope: m["scope"].as_str().unwrap_or("global").to_string(),
-            owner_type: m["owner_type"].as_str().unwrap_or("user").to_string(),
-            owner_id: m["owner_id"].as_str().unwrap_or("default").to_string(),
-            kind: m["kind"].as_str().unwrap_or("note").to_string(),
-            tier: m["tier"].as_str().unwrap_or("short_term").to_string(),
-            polarity: m["polarity"].as_i64().unwrap_or(1) as i32,
-            key: m["key"].as_str().map(|s| s.to_string()),
-            text: m["text"]
-                .as_str()
-                .ok_or_else(|| Error::other("Missing text"))?
-                .to_string(),
-            status: m["status"].as_str().unwrap_or("provisional").to_string(),
-            confidence: m["confidence"].as_f64(),
-            expires_at: m["expires_at"].as_str().map(|s| s.to_string()),
-            embedding: None,
-            created_at: m["created_at"]
-                .as_str()
-                .unwrap_or(&chrono::Utc::now().to_rfc3339())
-                .to_string(),
-            updated_at: m["updated_at"]
-                .as_str()
-                .unwrap_or(&chrono::Utc::now().to_rfc3339())
-                .to_string(),
+        let memory: ExportedMemory = match serde_json::from_str(&line) {
+            Ok(m) => m,
+            Err(_) => {
+                skipped += 1;
+                continue;
+            }
         };
 
-        db.insert_memory(&memory)?;
-        imported += 1;
+        match store(project_root, &memory)? {
+            Some(true) => reinforced += 1,
+            Some(false) => imported += 1,
+            None => skipped += 1,
+        }
     }
 
-    println!(
-        "Imported {} memories, skipped {} duplicates",
-        imported, skipped
-    );
+    Ok((imported, reinforced, skipped))
+}
 
-    Ok(())
+/// Parse the original whole-document schema. Not streamed line-by-line —
+/// it's one top-level JSON value — but kept for exports produced before
+/// NDJSON support existed. Input that isn't one document but whose first
+/// line is a memory object (what `export` writes) is imported as NDJSON.
+fn import_document(
+    project_root: &Path,
+    mut reader: BufReader<Box<dyn Read>>,
+) -> Result<(i32, i32, i32), Error> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+
+    let doc: ExportDocument = match serde_json::from_slice(&buf) {
+        Ok(doc) => doc,
+        Err(e) => {
+            let first_line = buf
+                .split(|b| *b == b'\n')
+                .find(|line| !line.iter().all(u8::is_ascii_whitespace));
+            if first_line.is_some_and(|line| serde_json::from_slice::<ExportedMemory>(line).is_ok()) {
+                let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(buf));
+                return import_ndjson(project_root, BufReader::new(reader));
+            }
+            return Err(Error::ConfigParse(format!("invalid export document: {e}")));
+        }
+    };
+
+    if doc.version != SUPPORTED_VERSION {
+        return Err(Error::ConfigParse(format!(
+            "unsupported export version '{}' (expected '{}')",
+            doc.version, SUPPORTED_VERSION
+        )));
+    }
+
+    let mut imported = 0;
+    let mut reinforced = 0;
+    let mut skipped = 0;
+
+    for memory in &doc.memories {
+        match store(project_root, memory)? {
+            Some(true) => reinforced += 1,
+            Some(false) => imported += 1,
+            None => skipped += 1,
+        }
+    }
+
+    Ok((imported, reinforced, skipped))
+}
+
+/// Insert or reinforce one memory through the existing dedup path.
+/// Returns `None` (counted as skipped) when the record is missing a
+/// required field.
+fn store(project_root: &Path, memory: &ExportedMemory) -> Result<Option<bool>, Error> {
+    let (Some(memory_type), Some(content)) = (&memory.memory_type, &memory.content) else {
+        return Ok(None);
+    };
+
+    let (_id, deduplicated, _use_count) =
+        storage::store_memory(project_root, memory_type, content, &memory.tags)?;
+    Ok(Some(deduplicated))
+}
+
+/// Open `path` (or stdin if `None`) and transparently unwrap `.gz`,
+/// `.zst`, or `.br` compression. Detected by extension first, falling
+/// back to magic-byte sniffing of the first few bytes — needed for
+/// stdin, which has no filename to go by.
+fn open_decompressed(path: Option<&PathBuf>) -> Result<Box<dyn Read>, Error> {
+    let raw: Box<dyn Read> = match path {
+        Some(path) => Box::new(File::open(path)?),
+        None => Box::new(std::io::stdin()),
+    };
+
+    let by_extension = path.and_then(|p| p.extension()).and_then(|e| e.to_str());
+    match by_extension {
+        Some("gz") => return Ok(Box::new(GzDecoder::new(raw))),
+        Some("zst") => return Ok(Box::new(zstd::stream::read::Decoder::new(raw)?)),
+        Some("br") => return Ok(Box::new(brotli::Decompressor::new(raw, 4096))),
+        _ => {}
+    }
+
+    // No recognized extension (or reading from stdin): sniff magic
+    // bytes instead. Brotli has no reliable magic number, so it's only
+    // detected by extension above.
+    let mut buffered = BufReader::new(raw);
+    let peek = buffered.fill_buf()?;
+
+    if peek.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(GzDecoder::new(buffered)))
+    } else if peek.starts_with(&ZSTD_MAGIC) {
+        Ok(Box::new(zstd::stream::read::Decoder::new(buffered)?))
+    } else {
+        Ok(Box::new(buffered))
+    }
 }
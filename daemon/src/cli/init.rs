@@ -2,6 +2,7 @@
 
 use std::path::PathBuf;
 
+use crate::cli::backfill;
 use crate::config::{ProjectConfig, ProjectsRegistry};
 use crate::db::Database;
 use crate::error::Error;
@@ -29,14 +30,14 @@ pub async fn run(skip_history: bool) -> Result<(), Error> {
         project_id: project_id.clone(),
         root_path: cwd.clone(),
         initialized_at: chrono::Utc::now().to_rfc3339(),
+        tags: Vec::new(),
     });
     registry.save()?;
     println!("Registered project: {}", project_id);
 
     if !skip_history {
         println!("Scanning for historical logs...");
-        // TODO: Implement historical log ingestion
-        println!("Historical log ingestion not yet implemented");
+        backfill::run(&cwd).await?;
     }
 
     println!("Squirrel initialized successfully!");
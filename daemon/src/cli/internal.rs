@@ -5,7 +5,11 @@ use std::process::Command;
 
 use tracing::debug;
 
+use crate::config::Config;
 use crate::error::Error;
+use crate::extensions::ExtensionRegistry;
+use crate::fsignore::IgnoreSet;
+use crate::storage::{self, ChangeRouter};
 
 /// Show diff summary before push (called by pre-push hook).
 /// AI reads this output and decides if docs need updating.
@@ -57,6 +61,9 @@ pub fn docguard_check() -> Result<bool, Error> {
         println!();
     }
 
+    print_monorepo_doc_debt(&project_root);
+    print_extension_debt_findings(&project_root);
+
     println!(" → Review if any docs need updating based on these changes.");
     println!("═══════════════════════════════════════════════════════════════");
     println!();
@@ -65,30 +72,85 @@ pub fn docguard_check() -> Result<bool, Error> {
     Ok(true)
 }
 
-/// Find project root by walking up directories looking for .sqrl.
-fn find_project_root() -> Option<PathBuf> {
-    let cwd = std::env::current_dir().ok()?;
-    let mut current = cwd.as_path();
+/// In a monorepo with configured subprojects, attribute changed files to
+/// their owning group and print only groups whose code changed but whose
+/// own docs didn't (instead of the single global debt signal above).
+fn print_monorepo_doc_debt(project_root: &PathBuf) {
+    let Ok(config) = Config::load(project_root) else {
+        return;
+    };
+    if config.subprojects.is_empty() {
+        return;
+    }
 
-    loop {
-        if current.join(".sqrl").exists() {
-            return Some(current.to_path_buf());
+    let changed_files = get_changed_files_for_push();
+    if changed_files.is_empty() {
+        return;
+    }
+
+    let router = ChangeRouter::new(config.subprojects);
+    let debt_groups = storage::groups_with_doc_debt(&router, &changed_files);
+
+    println!(" Per-subproject doc debt:");
+    if debt_groups.is_empty() {
+        println!("   (none — every subproject with code changes also touched its docs)");
+    } else {
+        for group in &debt_groups {
+            println!("   {} — code changed, docs did not", group);
         }
-        current = current.parent()?;
     }
+    println!();
 }
 
-/// Get list of commits that will be pushed (not yet on remote).
-fn get_unpushed_commits() -> Vec<String> {
-    // Get the upstream branch
+/// Run any registered `DebtCheckExtension`s against the files being pushed
+/// and print their findings alongside the built-in doc-debt signals.
+fn print_extension_debt_findings(project_root: &PathBuf) {
+    let registry = ExtensionRegistry::built_in();
+    let changed_files = get_changed_files_for_push();
+    if changed_files.is_empty() {
+        return;
+    }
+
+    let findings = registry.run_debt_checks(project_root, &changed_files);
+    if findings.is_empty() {
+        return;
+    }
+
+    println!(" Extension debt checks:");
+    for finding in &findings {
+        println!("   [{}] {}", finding.check_name, finding.message);
+    }
+    println!();
+}
+
+/// Get the list of files changed by commits being pushed (relative paths).
+fn get_changed_files_for_push() -> Vec<String> {
+    let upstream_ref = upstream_ref();
+
+    let output = Command::new("git")
+        .args(["diff", "--name-only", &format!("{}..HEAD", upstream_ref)])
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Resolve the upstream ref to diff against (falls back to origin/main or
+/// origin/master when the current branch has no upstream configured).
+fn upstream_ref() -> String {
     let upstream = Command::new("git")
         .args(["rev-parse", "--abbrev-ref", "@{upstream}"])
         .output();
 
-    let upstream_ref = match upstream {
+    match upstream {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
         _ => {
-            // No upstream, compare against origin/main or origin/master
             let main_exists = Command::new("git")
                 .args(["rev-parse", "--verify", "origin/main"])
                 .output()
@@ -101,7 +163,25 @@ fn get_unpushed_commits() -> Vec<String> {
                 "origin/master".to_string()
             }
         }
-    };
+    }
+}
+
+/// Find project root by walking up directories looking for .sqrl.
+fn find_project_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    let mut current = cwd.as_path();
+
+    loop {
+        if current.join(".sqrl").exists() {
+            return Some(current.to_path_buf());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Get list of commits that will be pushed (not yet on remote).
+fn get_unpushed_commits() -> Vec<String> {
+    let upstream_ref = upstream_ref();
 
     // Get commits between upstream and HEAD
     let output = Command::new("git")
@@ -119,27 +199,7 @@ fn get_unpushed_commits() -> Vec<String> {
 
 /// Get diff stats for changes being pushed.
 fn get_diff_stats_for_push() -> Vec<String> {
-    // Get the upstream branch
-    let upstream = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "@{upstream}"])
-        .output();
-
-    let upstream_ref = match upstream {
-        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
-        _ => {
-            let main_exists = Command::new("git")
-                .args(["rev-parse", "--verify", "origin/main"])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false);
-
-            if main_exists {
-                "origin/main".to_string()
-            } else {
-                "origin/master".to_string()
-            }
-        }
-    };
+    let upstream_ref = upstream_ref();
 
     // Get diff stat
     let output = Command::new("git")
@@ -166,10 +226,16 @@ fn get_diff_stats_for_push() -> Vec<String> {
     }
 }
 
-/// Find documentation files in the project.
+/// Find documentation files in the project, respecting `.gitignore`,
+/// `.git/info/exclude`, `.sqrlignore`, and the project's configured
+/// `docs.exclude_paths` (see `fsignore::IgnoreSet`) instead of a
+/// hard-coded substring blocklist.
 fn find_doc_files(project_root: &PathBuf) -> Vec<String> {
     let mut docs = Vec::new();
 
+    let docs_config = Config::load(project_root).map(|c| c.docs).unwrap_or_default();
+    let ignore = IgnoreSet::load(project_root, &docs_config);
+
     // Common doc locations
     let doc_patterns = [
         "README.md",
@@ -185,12 +251,7 @@ fn find_doc_files(project_root: &PathBuf) -> Vec<String> {
             for entry in entries.flatten() {
                 if let Ok(relative) = entry.strip_prefix(project_root) {
                     let path_str = relative.to_string_lossy().to_string();
-                    // Skip node_modules, target, etc.
-                    if !path_str.contains("node_modules")
-                        && !path_str.contains("target/")
-                        && !path_str.contains(".git/")
-                        && !docs.contains(&path_str)
-                    {
+                    if !ignore.is_ignored(relative, entry.is_dir()) && !docs.contains(&path_str) {
                         docs.push(path_str);
                     }
                 }
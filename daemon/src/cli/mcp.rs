@@ -1,5 +1,6 @@
 //! MCP server (stdio transport).
 
+use crate::cli::search::{lexical_search, Hit};
 use crate::config::Config;
 use crate::db::Database;
 use crate::error::Error;
@@ -12,103 +13,244 @@ pub async fn run() -> Result<(), Error> {
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    let _db = Database::open(&db_path)?;
+    let db = Database::open(&db_path)?;
 
-    // TODO: Implement MCP server with rmcp
-    // For now, just a placeholder
-    tracing::info!("MCP server not yet implemented");
     tracing::info!("Waiting for input on stdin...");
 
-    // Keep process alive for testing
-    let mut input = String::new();
+    // Read whole JSON values off stdin rather than one line at a time, so a
+    // pretty-printed or otherwise multi-line request still parses: each
+    // line is appended to a buffer and re-attempted until it is either a
+    // complete value or obviously malformed.
+    let mut buffer = String::new();
     loop {
-        input.clear();
-        if std::io::stdin().read_line(&mut input).is_err() {
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).is_err() {
             break;
         }
-        if input.is_empty() {
+        if line.is_empty() {
+            // EOF.
             break;
         }
+        buffer.push_str(&line);
 
-        // Parse JSON-RPC request
-        if let Ok(request) = serde_json::from_str::<serde_json::Value>(&input) {
-            let method = request["method"].as_str().unwrap_or("");
-            let id = &request["id"];
-
-            let response = match method {
-                "initialize" => {
-                    serde_json::json!({
-                        "jsonrpc": "2.0",
-                        "id": id,
-                        "result": {
-                            "protocolVersion": "2024-11-05",
-                            "capabilities": {
-                                "tools": {}
-                            },
-                            "serverInfo": {
-                                "name": "squirrel",
-                                "version": "0.1.0"
-                            }
-                        }
-                    })
-                }
-                "tools/list" => {
-                    serde_json::json!({
-                        "jsonrpc": "2.0",
-                        "id": id,
-                        "result": {
-                            "tools": [
-                                {
-                                    "name": "search_memories",
-                                    "description": "Search for relevant memories",
-                                    "inputSchema": {
-                                        "type": "object",
-                                        "properties": {
-                                            "query": {
-                                                "type": "string",
-                                                "description": "Search query"
-                                            }
-                                        },
-                                        "required": ["query"]
-                                    }
-                                },
-                                {
-                                    "name": "add_memory",
-                                    "description": "Add a new memory",
-                                    "inputSchema": {
-                                        "type": "object",
-                                        "properties": {
-                                            "text": {
-                                                "type": "string",
-                                                "description": "Memory text"
-                                            },
-                                            "kind": {
-                                                "type": "string",
-                                                "description": "Memory kind"
-                                            }
-                                        },
-                                        "required": ["text"]
-                                    }
-                                }
-                            ]
+        if buffer.trim().is_empty() {
+            buffer.clear();
+            continue;
+        }
+
+        let value: serde_json::Value = match serde_json::from_str(buffer.trim()) {
+            Ok(value) => value,
+            Err(err) if err.is_eof() => {
+                // Value isn't complete yet; keep accumulating lines.
+                continue;
+            }
+            Err(err) => {
+                tracing::warn!("Failed to parse JSON-RPC request: {err}");
+                buffer.clear();
+                continue;
+            }
+        };
+        buffer.clear();
+
+        // A batch is a JSON array of requests (some of which may be
+        // notifications); respond with the array of non-notification
+        // responses, or nothing at all if every request was a notification.
+        let is_batch = value.is_array();
+        let responses: Vec<serde_json::Value> = match value {
+            serde_json::Value::Array(requests) => {
+                requests.iter().filter_map(|request| handle_request(&db, request)).collect()
+            }
+            request => handle_request(&db, &request).into_iter().collect(),
+        };
+
+        if responses.is_empty() {
+            continue;
+        }
+        if !is_batch {
+            println!("{}", serde_json::to_string(&responses[0])?);
+        } else {
+            println!("{}", serde_json::to_string(&responses)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle one JSON-RPC request, returning its response unless the request
+/// is a notification (no `id`), in which case nothing is sent back.
+fn handle_request(db: &Database, request: &serde_json::Value) -> Option<serde_json::Value> {
+    let method = request["method"].as_str().unwrap_or("");
+    let id = request.get("id").cloned();
+    let is_notification = id.is_none();
+    let id = id.unwrap_or(serde_json::Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(serde_json::json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {
+                "tools": {}
+            },
+            "serverInfo": {
+                "name": "squirrel",
+                "version": "0.1.0"
+            }
+        })),
+        "tools/list" => Ok(tools_list()),
+        "tools/call" => handle_tools_call(db, &request["params"]),
+        _ => Err(RpcError::new(-32601, "Method not found")),
+    };
+
+    if is_notification {
+        return None;
+    }
+
+    Some(match result {
+        Ok(result) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        }),
+        Err(err) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": err.code,
+                "message": err.message,
+            }
+        }),
+    })
+}
+
+fn tools_list() -> serde_json::Value {
+    serde_json::json!({
+        "tools": [
+            {
+                "name": "search_memories",
+                "description": "Search for relevant memories",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "Search query"
                         }
-                    })
+                    },
+                    "required": ["query"]
                 }
-                _ => {
-                    serde_json::json!({
-                        "jsonrpc": "2.0",
-                        "id": id,
-                        "error": {
-                            "code": -32601,
-                            "message": "Method not found"
+            },
+            {
+                "name": "add_memory",
+                "description": "Add a new memory",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "text": {
+                            "type": "string",
+                            "description": "Memory text"
+                        },
+                        "kind": {
+                            "type": "string",
+                            "description": "Memory kind"
                         }
-                    })
+                    },
+                    "required": ["text"]
                 }
-            };
+            }
+        ]
+    })
+}
 
-            println!("{}", serde_json::to_string(&response)?);
-        }
+/// A JSON-RPC error: `-32602` for arguments that fail a tool's declared
+/// `inputSchema`, `-32603` for anything that goes wrong past validation
+/// (wrapping the underlying [`Error`]).
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
     }
 
-    Ok(())
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(-32602, message)
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(err: Error) -> Self {
+        Self::new(-32603, err.to_string())
+    }
+}
+
+/// Dispatch `tools/call`: look up the tool by `params.name`, validate
+/// `params.arguments` against its declared `inputSchema`, and run it.
+fn handle_tools_call(db: &Database, params: &serde_json::Value) -> Result<serde_json::Value, RpcError> {
+    let name = params["name"]
+        .as_str()
+        .ok_or_else(|| RpcError::invalid_params("missing required field: name"))?;
+    let arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
+
+    match name {
+        "search_memories" => call_search_memories(db, &arguments),
+        "add_memory" => call_add_memory(db, &arguments),
+        other => Err(RpcError::invalid_params(format!("unknown tool: {other}"))),
+    }
+}
+
+fn call_search_memories(db: &Database, arguments: &serde_json::Value) -> Result<serde_json::Value, RpcError> {
+    let query = arguments["query"]
+        .as_str()
+        .ok_or_else(|| RpcError::invalid_params("'query' is required and must be a string"))?;
+
+    let hits = lexical_search(db, query, None, None)?;
+    Ok(content_result(format_hits(&hits)))
+}
+
+fn call_add_memory(db: &Database, arguments: &serde_json::Value) -> Result<serde_json::Value, RpcError> {
+    let text = arguments["text"]
+        .as_str()
+        .ok_or_else(|| RpcError::invalid_params("'text' is required and must be a string"))?;
+    let kind = arguments.get("kind").and_then(|v| v.as_str()).unwrap_or("note");
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    db.conn()
+        .execute(
+            "INSERT INTO memories (id, kind, tier, text, status, created_at, updated_at) \
+             VALUES (?1, ?2, 'working', ?3, 'provisional', ?4, ?4)",
+            rusqlite::params![id, kind, text, now],
+        )
+        .map_err(Error::from)?;
+
+    Ok(content_result(format!("Stored memory {}", &id[..8])))
+}
+
+/// Wrap `text` as a single MCP `content` text block.
+fn content_result(text: String) -> serde_json::Value {
+    serde_json::json!({
+        "content": [
+            { "type": "text", "text": text }
+        ]
+    })
+}
+
+fn format_hits(hits: &[Hit]) -> String {
+    if hits.is_empty() {
+        return "No memories found.".to_string();
+    }
+
+    let mut out = String::new();
+    for hit in hits {
+        out.push_str(&format!(
+            "[{}] ({}/{}, {}) {}\n",
+            &hit.id[..8.min(hit.id.len())],
+            hit.kind,
+            hit.tier,
+            hit.status,
+            hit.text
+        ));
+    }
+    out
 }
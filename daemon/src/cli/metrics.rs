@@ -0,0 +1,89 @@
+//! Prometheus text-exposition metrics export (`sqrl metrics`).
+
+use std::path::Path;
+
+use crate::config::Config;
+use crate::db::Database;
+use crate::error::Error;
+use crate::storage;
+
+/// Run metrics command. Emits Prometheus exposition format, built from the
+/// same aggregation queries `status`'s `print_db_stats` uses so the numbers
+/// agree with what `status` prints.
+pub async fn run(project_root: &Path) -> Result<(), Error> {
+    let mut out = String::new();
+
+    let local_db_path = project_root.join(".sqrl").join("squirrel.db");
+    if local_db_path.exists() {
+        let db = Database::open(&local_db_path)?;
+        write_status_metrics(&mut out, &db)?;
+    }
+
+    write_type_metrics(&mut out, project_root)?;
+
+    let socket_path = Config::load()?.daemon.socket_path;
+    let daemon_up = if Path::new(&socket_path).exists() { 1 } else { 0 };
+    out.push_str("# HELP squirrel_daemon_up Whether the daemon's IPC socket is present.\n");
+    out.push_str("# TYPE squirrel_daemon_up gauge\n");
+    out.push_str(&format!("squirrel_daemon_up {}\n", daemon_up));
+
+    print!("{}", out);
+    Ok(())
+}
+
+/// Per-status memory counts and per-state episode counts, the same
+/// `GROUP BY` queries `status`'s `print_db_stats` runs against the local
+/// project database.
+fn write_status_metrics(out: &mut String, db: &Database) -> Result<(), Error> {
+    out.push_str("# HELP squirrel_memories_total Memory count by status.\n");
+    out.push_str("# TYPE squirrel_memories_total gauge\n");
+    let mut stmt = db
+        .conn()
+        .prepare("SELECT status, COUNT(*) as count FROM memories GROUP BY status")?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let status: String = row.get("status")?;
+        let count: i64 = row.get("count")?;
+        out.push_str(&format!(
+            "squirrel_memories_total{{status=\"{}\"}} {}\n",
+            status, count
+        ));
+    }
+
+    out.push_str("# HELP squirrel_episodes Episode count by processing state.\n");
+    out.push_str("# TYPE squirrel_episodes gauge\n");
+    let mut stmt = db
+        .conn()
+        .prepare("SELECT processed, COUNT(*) as count FROM episodes GROUP BY processed")?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let processed: i32 = row.get("processed")?;
+        let count: i64 = row.get("count")?;
+        let state = if processed == 0 { "pending" } else { "processed" };
+        out.push_str(&format!("squirrel_episodes{{state=\"{}\"}} {}\n", state, count));
+    }
+
+    Ok(())
+}
+
+/// Per-type memory counts and unresolved doc debt, from the real memory
+/// store (`storage::get_memory_counts`, `storage::get_unresolved_doc_debt`)
+/// so the numbers don't drift from a separately-maintained copy.
+fn write_type_metrics(out: &mut String, project_root: &Path) -> Result<(), Error> {
+    out.push_str("# HELP squirrel_memories_by_type Memory count by memory_type.\n");
+    out.push_str("# TYPE squirrel_memories_by_type gauge\n");
+    let counts = storage::get_memory_counts(project_root)?;
+    for (memory_type, count) in &counts {
+        out.push_str(&format!(
+            "squirrel_memories_by_type{{type=\"{}\"}} {}\n",
+            memory_type, count
+        ));
+    }
+
+    out.push_str("# HELP squirrel_doc_debt_unresolved Count of unresolved doc debt entries.\n");
+    out.push_str("# TYPE squirrel_doc_debt_unresolved gauge\n");
+    let unresolved = storage::get_unresolved_doc_debt(project_root)?.len();
+    out.push_str(&format!("squirrel_doc_debt_unresolved {}\n", unresolved));
+
+    Ok(())
+}
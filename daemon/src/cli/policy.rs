@@ -1,6 +1,7 @@
 //! Manage memory policy.
 
 use crate::error::Error;
+use crate::mcp::policy as mcp_policy;
 
 /// Run policy command.
 pub async fn run(action: &str) -> Result<(), Error> {
@@ -32,9 +33,30 @@ pub async fn run(action: &str) -> Result<(), Error> {
         }
 
         "reload" => {
-            // TODO: Signal daemon to reload policy
-            println!("Policy reload not yet implemented.");
-            println!("Restart the daemon to apply policy changes.");
+            // The running `mcp-serve` process watches both policy files and
+            // reloads on any change; touching them (without altering their
+            // content) is enough to force that without restarting it.
+            let cwd = std::env::current_dir()?;
+            let project_policy = mcp_policy::project_policy_path(&cwd);
+            let global_policy = mcp_policy::global_policy_path();
+
+            let mut touched = false;
+            if project_policy.exists() {
+                mcp_policy::touch(&project_policy)?;
+                touched = true;
+            }
+            if let Some(global_policy) = global_policy {
+                if global_policy.exists() {
+                    mcp_policy::touch(&global_policy)?;
+                    touched = true;
+                }
+            }
+
+            if touched {
+                println!("Policy reload requested.");
+            } else {
+                println!("No policy file found to reload.");
+            }
         }
 
         _ => {
@@ -0,0 +1,61 @@
+//! Heal a project database: integrity check, compaction, and re-dedup.
+
+use std::path::Path;
+
+use crate::error::Error;
+use crate::storage;
+
+/// Run repair command. Prints a `status`-style summary of what was fixed
+/// (or, under `dry_run`, what would be).
+pub fn run(project_root: &Path, dry_run: bool) -> Result<(), Error> {
+    let report = storage::repair(project_root, dry_run)?;
+
+    if dry_run {
+        println!("Repair (dry run) for {}", project_root.display());
+    } else {
+        println!("Repair for {}", project_root.display());
+    }
+    println!("=======================================");
+    println!(
+        "  Integrity check: {}",
+        if report.integrity_ok {
+            "ok".to_string()
+        } else {
+            format!("FAILED ({})", report.integrity_detail)
+        }
+    );
+    println!(
+        "  Vacuum: {}",
+        if dry_run {
+            "planned"
+        } else if report.vacuumed {
+            "done"
+        } else {
+            "skipped"
+        }
+    );
+    println!(
+        "  Indexes rebuilt: {}{}",
+        report.indexes_rebuilt,
+        if dry_run { " (planned)" } else { "" }
+    );
+    println!(
+        "  Duplicate memories merged: {}{}",
+        report.duplicates_merged,
+        if dry_run { " (planned)" } else { "" }
+    );
+    println!(
+        "  Stale doc_debt rows dropped: {}{}",
+        report.stale_doc_debt_dropped,
+        if dry_run { " (planned)" } else { "" }
+    );
+
+    if !report.integrity_ok {
+        return Err(Error::ConfigParse(format!(
+            "database integrity check failed: {}",
+            report.integrity_detail
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Background memory-scrub worker (modeled on Garage's scrub worker):
+//! periodically walks the global memory DB re-running deduplication and
+//! re-embedding entries whose `embedding_model` no longer matches
+//! `config.llm.embedding_model`, throttled by a live-adjustable
+//! *tranquility* knob so it doesn't compete with live episode processing.
+//! See `sqrl config daemon.scrub_tranquility` / `cli::control`.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::control::Tranquility;
+use crate::cli::worker::{Worker, WorkerState};
+use crate::config::Config;
+use crate::db::Database;
+use crate::embedder;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+
+/// How often a full pass is re-triggered after the last one completed.
+/// Fixed: no config setting overrides it.
+const DEFAULT_SCRUB_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Resumable progress through the memory table, persisted so an
+/// interrupted scrub (daemon restart, crash) resumes where it left off
+/// instead of starting the whole DB over.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScrubCursor {
+    /// Highest `rowid` scanned so far in the current pass.
+    last_rowid: i64,
+    /// Bumped each time a full pass wraps back to the start.
+    generation: u64,
+    /// RFC3339 timestamp of the last completed pass, if any.
+    last_completed_at: Option<String>,
+}
+
+impl Default for ScrubCursor {
+    fn default() -> Self {
+        Self {
+            last_rowid: 0,
+            generation: 0,
+            last_completed_at: None,
+        }
+    }
+}
+
+fn cursor_path() -> Result<PathBuf, Error> {
+    Ok(GlobalConfig::dir()?.join("scrub_state.json"))
+}
+
+fn load_cursor() -> ScrubCursor {
+    cursor_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_cursor(cursor: &ScrubCursor) -> Result<(), Error> {
+    let path = cursor_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(cursor)?)?;
+    Ok(())
+}
+
+/// The memory-scrub worker. Registered alongside the file-event/idle
+/// workers in `watch::run_daemon` via `WorkerManager`; its progress is
+/// surfaced through `sqrl workers` via [`Worker::progress`].
+pub struct ScrubWorker {
+    tranquility: Tranquility,
+    cursor: ScrubCursor,
+    total_rows: i64,
+}
+
+impl ScrubWorker {
+    pub fn new(tranquility: Tranquility) -> Self {
+        Self {
+            tranquility,
+            cursor: load_cursor(),
+            total_rows: 0,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Worker for ScrubWorker {
+    fn name(&self) -> &str {
+        "memory-scrub"
+    }
+
+    async fn work(&mut self) -> Result<WorkerState, Error> {
+        let db_path = Config::global_db_path();
+        if !db_path.exists() {
+            return Ok(WorkerState::Idle(Some(DEFAULT_SCRUB_INTERVAL)));
+        }
+
+        // The previous pass finished and we've been idling out
+        // `DEFAULT_SCRUB_INTERVAL` since; being woken up again means that
+        // wait is over, so start a fresh generation from the top rather
+        // than resuming from the old tail (which would only ever pick up
+        // rows appended since, e.g. after `llm.embedding_model` changes).
+        if self.cursor.last_completed_at.is_some() {
+            self.cursor.last_rowid = 0;
+            self.cursor.last_completed_at = None;
+            save_cursor(&self.cursor)?;
+        }
+
+        let db = Database::open(&db_path)?;
+        self.total_rows = db
+            .conn()
+            .query_row("SELECT COUNT(*) FROM memories", [], |row| row.get(0))
+            .unwrap_or(0);
+
+        let next_row: Option<(i64, String, Option<String>)> = db
+            .conn()
+            .query_row(
+                "SELECT rowid, id, embedding_model FROM memories \
+                 WHERE rowid > ?1 ORDER BY rowid LIMIT 1",
+                rusqlite::params![self.cursor.last_rowid],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .ok();
+
+        let Some((rowid, memory_id, embedding_model)) = next_row else {
+            // Reached the end of this pass. Wait out the interval before
+            // starting the next one rather than busy-looping at rowid 0.
+            self.cursor.generation += 1;
+            self.cursor.last_completed_at = Some(chrono::Utc::now().to_rfc3339());
+            save_cursor(&self.cursor)?;
+            return Ok(WorkerState::Idle(Some(DEFAULT_SCRUB_INTERVAL)));
+        };
+
+        let started = Instant::now();
+        // A single row failing to re-embed (endpoint down, transient
+        // network error, ...) shouldn't count against this worker's
+        // `MAX_CONSECUTIVE_ERRORS` and take the whole scrub pass down —
+        // log it and move on; the row gets another chance next generation.
+        if let Err(e) = rescrub_one(&db, &memory_id, embedding_model.as_deref()) {
+            tracing::warn!(memory_id = %memory_id, error = %e, "Failed to rescrub memory, skipping");
+        }
+        let elapsed = started.elapsed();
+
+        self.cursor.last_rowid = rowid;
+        self.cursor.last_completed_at = None;
+        save_cursor(&self.cursor)?;
+
+        let tranquility = self.tranquility.get();
+        if tranquility > 0 {
+            tokio::time::sleep(elapsed * tranquility as u32).await;
+        }
+
+        Ok(WorkerState::Busy)
+    }
+
+    fn progress(&self) -> Option<String> {
+        let percent = if self.total_rows > 0 {
+            (self.cursor.last_rowid as f64 / self.total_rows as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        Some(format!(
+            "gen {} · {:.0}% scanned · tranquility={}",
+            self.cursor.generation,
+            percent,
+            self.tranquility.get()
+        ))
+    }
+}
+
+/// Re-embed `memory_id` if its stored `embedding_model` no longer
+/// matches the configured one, then merge it into an existing near-
+/// duplicate (by embedding cosine similarity) if one is found.
+fn rescrub_one(db: &Database, memory_id: &str, embedding_model: Option<&str>) -> Result<(), Error> {
+    let config = Config::load()?;
+    let configured_model = config.llm.embedding_model.trim();
+
+    if configured_model.is_empty() {
+        return Ok(());
+    }
+
+    if embedding_model != Some(configured_model) {
+        if let Some(embedder) = embedder::from_config(&config) {
+            let text: String = db.conn().query_row(
+                "SELECT text FROM memories WHERE id = ?1",
+                rusqlite::params![memory_id],
+                |row| row.get(0),
+            )?;
+            let vector = embedder.embed(&text)?;
+            let blob = embedder::pack(&vector);
+            db.conn().execute(
+                "UPDATE memories SET embedding = ?1, embedding_model = ?2 WHERE id = ?3",
+                rusqlite::params![blob, configured_model, memory_id],
+            )?;
+        }
+    }
+
+    merge_if_duplicate(db, memory_id)
+}
+
+/// Cosine-similarity threshold above which two memories are considered
+/// duplicates worth merging.
+const DUPLICATE_THRESHOLD: f32 = 0.98;
+
+/// Find the nearest other memory (same `kind`) by embedding similarity;
+/// if it's above [`DUPLICATE_THRESHOLD`], delete whichever of the two
+/// was created later, mirroring `storage::merge_duplicate_memories`'s
+/// keep-the-oldest rule.
+fn merge_if_duplicate(db: &Database, memory_id: &str) -> Result<(), Error> {
+    let row: Option<(String, Vec<u8>, String)> = db
+        .conn()
+        .query_row(
+            "SELECT kind, embedding, id FROM memories WHERE id = ?1 AND embedding IS NOT NULL",
+            rusqlite::params![memory_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .ok();
+    let Some((kind, embedding_blob, id)) = row else {
+        return Ok(());
+    };
+    let vector = embedder::unpack(&embedding_blob);
+
+    let mut stmt = db.conn().prepare(
+        "SELECT id, embedding FROM memories \
+         WHERE kind = ?1 AND id != ?2 AND embedding IS NOT NULL",
+    )?;
+    let mut rows = stmt.query(rusqlite::params![kind, id])?;
+
+    while let Some(row) = rows.next()? {
+        let other_id: String = row.get(0)?;
+        let other_blob: Vec<u8> = row.get(1)?;
+        let score = embedder::cosine_similarity(&vector, &embedder::unpack(&other_blob));
+        if score >= DUPLICATE_THRESHOLD {
+            // Keep whichever of the two was created first.
+            let (keep, drop): (String, String) = db.conn().query_row(
+                "SELECT a.id, b.id FROM memories a, memories b \
+                 WHERE a.id = ?1 AND b.id = ?2 AND a.created_at <= b.created_at",
+                rusqlite::params![id, other_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).unwrap_or((other_id.clone(), id.clone()));
+
+            db.conn()
+                .execute("DELETE FROM memories WHERE id = ?1", rusqlite::params![drop])?;
+            tracing::info!(kept = %keep, "Scrub merged duplicate memory");
+            break;
+        }
+    }
+
+    Ok(())
+}
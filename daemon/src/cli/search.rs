@@ -2,10 +2,30 @@
 
 use crate::config::Config;
 use crate::db::Database;
+use crate::embedder::{self, cosine_similarity, unpack};
 use crate::error::Error;
 
-/// Run memory search.
-pub async fn run(query: &str, kind: Option<&str>, tier: Option<&str>) -> Result<(), Error> {
+/// A single ranked search hit, common to both the lexical and semantic
+/// paths so `run` can print them identically. `pub(crate)` so other
+/// front ends (e.g. `cli::mcp`'s `search_memories` tool) can drive the
+/// same query logic instead of re-implementing it.
+pub(crate) struct Hit {
+    pub(crate) id: String,
+    pub(crate) kind: String,
+    pub(crate) tier: String,
+    pub(crate) text: String,
+    pub(crate) status: String,
+}
+
+/// Run memory search. `semantic` selects embedding-based cosine-similarity
+/// ranking over the lexical FTS5/`bm25` scan; it falls back to the lexical path
+/// when no embedder is configured (see `embedder::from_config`).
+pub async fn run(
+    query: &str,
+    kind: Option<&str>,
+    tier: Option<&str>,
+    semantic: bool,
+) -> Result<(), Error> {
     let db_path = Config::global_db_path();
     if !db_path.exists() {
         println!("No memories found. Run 'sqrl init' first.");
@@ -14,52 +34,212 @@ pub async fn run(query: &str, kind: Option<&str>, tier: Option<&str>) -> Result<
 
     let db = Database::open(&db_path)?;
 
-    // Build query
-    let mut sql = String::from("SELECT * FROM memories WHERE status IN ('provisional', 'active')");
-    let mut params: Vec<String> = Vec::new();
+    let hits = if semantic {
+        let config = Config::load()?;
+        match embedder::from_config(&config) {
+            Some(embedder) => semantic_search(&db, embedder.as_ref(), query, kind, tier)?,
+            None => {
+                println!(
+                    "No embedder configured (set llm.embedding_model); falling back to lexical search."
+                );
+                lexical_search(&db, query, kind, tier)?
+            }
+        }
+    } else {
+        lexical_search(&db, query, kind, tier)?
+    };
+
+    if hits.is_empty() {
+        println!("No memories found matching '{}'", query);
+    } else {
+        for hit in &hits {
+            println!("---");
+            println!("ID: {}", &hit.id[..8.min(hit.id.len())]);
+            println!("Kind: {} | Tier: {} | Status: {}", hit.kind, hit.tier, hit.status);
+            println!("Text: {}", hit.text);
+        }
+        println!("---");
+        println!("Found {} memories", hits.len());
+    }
+
+    Ok(())
+}
+
+const RESULT_LIMIT: usize = 20;
+
+/// Ranked FTS5 scan over `memories_fts` (a `content='memories'` external
+/// content table kept in sync by triggers on `memories`), ordered by
+/// `bm25()` relevance instead of recency. Replaces the original `text
+/// LIKE ?` scan so `cli::forget`'s `--query` match and `cli::mcp`'s
+/// `search_memories` tool both get tokenized, ranked matches instead of
+/// a crude substring scan. `db::Database` has no migration runner of its
+/// own (unlike `storage`, whose `run_migrations` creates the
+/// same kind of index for its own `memories` table), so [`ensure_fts_index`]
+/// creates this one lazily on first use instead.
+pub(crate) fn lexical_search(
+    db: &Database,
+    query: &str,
+    kind: Option<&str>,
+    tier: Option<&str>,
+) -> Result<Vec<Hit>, Error> {
+    ensure_fts_index(db)?;
+
+    let mut sql = String::from(
+        "SELECT m.id, m.kind, m.tier, m.text, m.status \
+         FROM memories_fts f JOIN memories m ON m.rowid = f.rowid \
+         WHERE memories_fts MATCH ?1 AND m.status IN ('provisional', 'active')",
+    );
+
+    if kind.is_some() {
+        sql.push_str(" AND m.kind = ?2");
+    }
+    if tier.is_some() {
+        sql.push_str(" AND m.tier = ?3");
+    }
+    sql.push_str(" ORDER BY bm25(memories_fts) LIMIT ?4");
+
+    let fts_query = sanitize_fts_query(query);
+    let mut stmt = db.conn().prepare(&sql)?;
+    let mut rows = stmt.query(rusqlite::params![
+        fts_query,
+        kind.unwrap_or_default(),
+        tier.unwrap_or_default(),
+        RESULT_LIMIT as i64,
+    ])?;
+
+    let mut hits = Vec::new();
+    while let Some(row) = rows.next()? {
+        hits.push(Hit {
+            id: row.get("id")?,
+            kind: row.get("kind")?,
+            tier: row.get("tier")?,
+            text: row.get("text")?,
+            status: row.get("status")?,
+        });
+    }
+    Ok(hits)
+}
+
+/// Create `memories_fts` and the triggers that keep it in sync with
+/// `memories`, if they don't already exist. Idempotent (every statement
+/// is `IF NOT EXISTS`/backfills only rows missing from the index), so
+/// it's cheap to call on every search rather than needing its own
+/// one-time migration step.
+fn ensure_fts_index(db: &Database) -> Result<(), Error> {
+    db.conn().execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts
+         USING fts5(text, content='memories', content_rowid='rowid');
+         CREATE TRIGGER IF NOT EXISTS memories_fts_ai AFTER INSERT ON memories BEGIN
+             INSERT INTO memories_fts(rowid, text) VALUES (new.rowid, new.text);
+         END;
+         CREATE TRIGGER IF NOT EXISTS memories_fts_ad AFTER DELETE ON memories BEGIN
+             INSERT INTO memories_fts(memories_fts, rowid, text) VALUES('delete', old.rowid, old.text);
+         END;
+         CREATE TRIGGER IF NOT EXISTS memories_fts_au AFTER UPDATE ON memories BEGIN
+             INSERT INTO memories_fts(memories_fts, rowid, text) VALUES('delete', old.rowid, old.text);
+             INSERT INTO memories_fts(rowid, text) VALUES (new.rowid, new.text);
+         END;
+         INSERT INTO memories_fts(rowid, text)
+         SELECT rowid, text FROM memories
+         WHERE rowid NOT IN (SELECT rowid FROM memories_fts);",
+    )?;
+    Ok(())
+}
+
+/// Quote each whitespace-separated term of a free-text query so stray
+/// FTS5 operators (`-`, `*`, `:`, unbalanced quotes, ...) in user input
+/// can't be interpreted as query syntax. Terms are implicitly ANDed by
+/// FTS5. Mirrors `storage::sanitize_fts_query`.
+fn sanitize_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
+/// Embed `query`, then rank rows with a non-null `embedding` by cosine
+/// similarity against it. `kind`/`tier` are pushed down into the SQL
+/// pre-filter the same as the lexical path; only the relevance ranking
+/// itself happens in Rust, over a bounded top-`RESULT_LIMIT` min-heap so
+/// memory stays flat regardless of how many rows have embeddings.
+fn semantic_search(
+    db: &Database,
+    embedder: &dyn embedder::Embedder,
+    query: &str,
+    kind: Option<&str>,
+    tier: Option<&str>,
+) -> Result<Vec<Hit>, Error> {
+    let query_vector = embedder.embed(query)?;
+
+    let mut sql = String::from(
+        "SELECT id, kind, tier, text, status, embedding FROM memories \
+         WHERE status IN ('provisional', 'active') AND embedding IS NOT NULL",
+    );
+    let mut params: Vec<String> = Vec::new();
     if let Some(k) = kind {
         sql.push_str(" AND kind = ?");
         params.push(k.to_string());
     }
-
     if let Some(t) = tier {
         sql.push_str(" AND tier = ?");
         params.push(t.to_string());
     }
 
-    // Text search (simple LIKE for now, vector search later)
-    sql.push_str(" AND text LIKE ?");
-    params.push(format!("%{}%", query));
-
-    sql.push_str(" ORDER BY updated_at DESC LIMIT 20");
-
     let mut stmt = db.conn().prepare(&sql)?;
     let param_refs: Vec<&dyn rusqlite::ToSql> =
         params.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
     let mut rows = stmt.query(param_refs.as_slice())?;
 
-    let mut count = 0;
+    // Bounded min-heap of size RESULT_LIMIT, keyed by score, so ranking a
+    // large `memories` table never holds more than RESULT_LIMIT candidates
+    // at once.
+    let mut top: std::collections::BinaryHeap<std::cmp::Reverse<ScoredHit>> =
+        std::collections::BinaryHeap::with_capacity(RESULT_LIMIT + 1);
+
     while let Some(row) = rows.next()? {
-        let id: String = row.get("id")?;
-        let kind: String = row.get("kind")?;
-        let tier: String = row.get("tier")?;
-        let text: String = row.get("text")?;
-        let status: String = row.get("status")?;
+        let embedding_blob: Vec<u8> = row.get("embedding")?;
+        let score = cosine_similarity(&query_vector, &unpack(&embedding_blob));
 
-        println!("---");
-        println!("ID: {}", &id[..8]);
-        println!("Kind: {} | Tier: {} | Status: {}", kind, tier, status);
-        println!("Text: {}", text);
-        count += 1;
-    }
+        let hit = Hit {
+            id: row.get("id")?,
+            kind: row.get("kind")?,
+            tier: row.get("tier")?,
+            text: row.get("text")?,
+            status: row.get("status")?,
+        };
 
-    if count == 0 {
-        println!("No memories found matching '{}'", query);
-    } else {
-        println!("---");
-        println!("Found {} memories", count);
+        top.push(std::cmp::Reverse(ScoredHit { score, hit }));
+        if top.len() > RESULT_LIMIT {
+            top.pop();
+        }
     }
 
-    Ok(())
+    let mut scored: Vec<ScoredHit> = top.into_iter().map(|r| r.0).collect();
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(scored.into_iter().map(|s| s.hit).collect())
+}
+
+/// A `Hit` with its similarity score, ordered by score so it can sit in a
+/// `BinaryHeap` (used in reverse, as a bounded min-heap).
+struct ScoredHit {
+    score: f32,
+    hit: Hit,
+}
+
+impl PartialEq for ScoredHit {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+impl Eq for ScoredHit {}
+impl PartialOrd for ScoredHit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for ScoredHit {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.partial_cmp(&other.score).unwrap_or(std::cmp::Ordering::Equal)
+    }
 }
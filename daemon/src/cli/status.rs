@@ -4,8 +4,9 @@ use crate::config::{Config, ProjectsRegistry};
 use crate::db::Database;
 use crate::error::Error;
 
-/// Run status command.
-pub async fn run() -> Result<(), Error> {
+/// Run status command, optionally restricted to projects carrying `tag`
+/// (e.g. `sqrl status --tag backend`).
+pub async fn run(tag: Option<&str>) -> Result<(), Error> {
     println!("Squirrel Status");
     println!("===============");
     println!();
@@ -28,12 +29,17 @@ pub async fn run() -> Result<(), Error> {
 
     // Projects
     let registry = ProjectsRegistry::load()?;
-    println!("Registered Projects: {}", registry.projects.len());
-    for project in &registry.projects {
+    let targets = registry.filter_by_tag(tag);
+    match tag {
+        Some(tag) => println!("Registered Projects tagged '{}': {}", tag, targets.len()),
+        None => println!("Registered Projects: {}", targets.len()),
+    }
+    for project in &targets {
         println!(
-            "  - {} ({})",
+            "  - {} ({}) [{}]",
             project.project_id,
-            project.root_path.display()
+            project.root_path.display(),
+            project.tags.join(", ")
         );
     }
     println!();
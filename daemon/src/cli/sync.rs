@@ -1,15 +1,81 @@
 //! Sync all projects with CLI configs.
+//!
+//! Renders each registered project's agent config files (`CLAUDE.md`,
+//! `.cursor/rules`, Codex instructions, ...) from a shared Tera template
+//! source under `.sqrl/templates/`, gated on which tools the project has
+//! enabled in `ToolsConfig`. Rendering is idempotent: everything outside the
+//! Squirrel-managed block is left untouched.
 
-use crate::config::ProjectsRegistry;
+use std::fs;
+use std::path::Path;
+
+use tera::{Context, Tera};
+
+use crate::config::{Config, ProjectsRegistry, ToolsConfig};
 use crate::error::Error;
 
-/// Run sync command.
-pub async fn run() -> Result<(), Error> {
-    println!("Syncing projects...");
+const MANAGED_START: &str = "<!-- >>> squirrel managed block >>> -->";
+const MANAGED_END: &str = "<!-- <<< squirrel managed block <<< -->";
+
+/// One agent config file Squirrel can render, gated on a `ToolsConfig` flag.
+struct AgentTarget {
+    /// Relative path (from project root) of the rendered file.
+    path: &'static str,
+    /// Template file name looked up under `.sqrl/templates/` before falling
+    /// back to `default_template`.
+    template_name: &'static str,
+    /// Default template source used when the project has no override.
+    default_template: &'static str,
+    /// Whether to render this target, given the project's tools config.
+    enabled: fn(&ToolsConfig) -> bool,
+}
+
+const AGENT_TARGETS: &[AgentTarget] = &[
+    AgentTarget {
+        path: "CLAUDE.md",
+        template_name: "CLAUDE.md.tera",
+        default_template: "## Squirrel memory protocol\n\n\
+            This project uses Squirrel for persistent memory. Call the \
+            `squirrel_get_memory` tool at session start and `squirrel_store_memory` \
+            whenever corrected.\n\n\
+            Doc paths tracked for debt: {{ doc_paths | join(sep=\", \") }}\n",
+        enabled: |t| t.claude_code,
+    },
+    AgentTarget {
+        path: ".cursor/rules",
+        template_name: "cursor-rules.tera",
+        default_template: "# Squirrel memory protocol\n\n\
+            Use the Squirrel MCP tools (`squirrel_get_memory`, `squirrel_store_memory`) \
+            to read and record project preferences.\n",
+        enabled: |t| t.cursor,
+    },
+    AgentTarget {
+        path: ".codex/instructions.md",
+        template_name: "codex-instructions.tera",
+        default_template: "# Squirrel memory protocol\n\n\
+            Project: {{ project_id }}\n\
+            Call `squirrel_get_memory` / `squirrel_store_memory` via MCP.\n",
+        enabled: |t| t.codex,
+    },
+];
+
+/// Run sync command, optionally restricted to projects carrying `tag`
+/// (e.g. `sqrl sync --tag backend`). Prints a per-project result line plus
+/// a rollup summary at the end.
+pub async fn run(tag: Option<&str>) -> Result<(), Error> {
+    match tag {
+        Some(tag) => println!("Syncing projects tagged '{}'...", tag),
+        None => println!("Syncing projects..."),
+    }
 
     let registry = ProjectsRegistry::load()?;
+    let targets = registry.filter_by_tag(tag);
+
+    let mut synced = 0;
+    let mut skipped = 0;
+    let mut failed = 0;
 
-    for project in &registry.projects {
+    for project in &targets {
         let sqrl_dir = project.root_path.join(".sqrl");
 
         if !sqrl_dir.exists() {
@@ -17,13 +83,92 @@ pub async fn run() -> Result<(), Error> {
                 "  {} - missing .sqrl directory, skipping",
                 project.project_id
             );
+            skipped += 1;
+            continue;
+        }
+
+        let result = Config::load(&project.root_path)
+            .and_then(|config| sync_project(&project.root_path, &project.project_id, &config));
+
+        match result {
+            Ok(rendered) => {
+                println!("  {} - synced ({} files)", project.project_id, rendered);
+                synced += 1;
+            }
+            Err(e) => {
+                println!("  {} - failed: {}", project.project_id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Sync complete: {} synced, {} skipped, {} failed ({} matched).",
+        synced,
+        skipped,
+        failed,
+        targets.len()
+    );
+    Ok(())
+}
+
+/// Render every enabled agent target for one project. Returns the number of
+/// files written.
+fn sync_project(project_root: &Path, project_id: &str, config: &Config) -> Result<usize, Error> {
+    let templates_dir = project_root.join(".sqrl").join("templates");
+
+    let mut ctx = Context::new();
+    ctx.insert("project_id", project_id);
+    ctx.insert("doc_paths", &config.docs.include_paths);
+
+    let mut rendered = 0;
+    for target in AGENT_TARGETS {
+        if !(target.enabled)(&config.tools) {
             continue;
         }
 
-        // TODO: Sync agent configs (CLAUDE.md, .cursorrules, etc.)
-        println!("  {} - synced", project.project_id);
+        let source = load_template_source(&templates_dir, target)?;
+        let body = Tera::one_off(&source, &ctx, false)
+            .map_err(|e| Error::ConfigParse(format!("failed to render {}: {e}", target.path)))?;
+
+        write_managed_file(&project_root.join(target.path), &body)?;
+        rendered += 1;
     }
 
-    println!("Sync complete.");
+    Ok(rendered)
+}
+
+/// Load a project's template override if present, else the built-in default.
+fn load_template_source(templates_dir: &Path, target: &AgentTarget) -> Result<String, Error> {
+    let override_path = templates_dir.join(target.template_name);
+    if override_path.exists() {
+        Ok(fs::read_to_string(override_path)?)
+    } else {
+        Ok(target.default_template.to_string())
+    }
+}
+
+/// Write `body` wrapped in a Squirrel-managed block, preserving any
+/// user-editable content outside it.
+fn write_managed_file(path: &Path, body: &str) -> Result<(), Error> {
+    let block = format!("{MANAGED_START}\n{}\n{MANAGED_END}", body.trim_end());
+
+    let final_content = if path.exists() {
+        let existing = fs::read_to_string(path)?;
+        match (existing.find(MANAGED_START), existing.find(MANAGED_END)) {
+            (Some(start), Some(end)) => {
+                let end = end + MANAGED_END.len();
+                format!("{}{}{}", &existing[..start], block, &existing[end..])
+            }
+            _ => format!("{}\n\n{}\n", existing.trim_end(), block),
+        }
+    } else {
+        format!("{}\n", block)
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, final_content)?;
     Ok(())
 }
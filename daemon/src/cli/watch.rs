@@ -1,11 +1,16 @@
 //! Watch daemon - watches Claude Code logs and sends episodes to Python service.
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::time::sleep;
+use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
+use crate::cli::control::{self, Command, Controller, PauseFlag};
+use crate::cli::scrub::ScrubWorker;
+use crate::cli::worker::{Worker, WorkerManager, WorkerState};
+use crate::config::Config;
 use crate::dashboard;
 use crate::error::Error;
 use crate::ipc::types::{ExistingProjectMemory, ExistingUserStyle};
@@ -22,6 +27,12 @@ const IDLE_CHECK_INTERVAL_SECS: u64 = 60;
 const POLL_INTERVAL_MS: u64 = 100;
 
 /// Run the watcher daemon (called by system service).
+///
+/// Previously a single hand-rolled loop draining file events, running
+/// `check_idle_sessions`, and periodically saving the `PositionStore` all
+/// inline with no visibility into any of them. Each concern is now its own
+/// [`Worker`], supervised by a [`WorkerManager`] so a stuck or panicking
+/// worker shows up in `sqrl workers` instead of silently going quiet.
 pub async fn run_daemon() -> Result<(), Error> {
     info!("Starting Squirrel watcher daemon");
 
@@ -32,11 +43,14 @@ pub async fn run_daemon() -> Result<(), Error> {
         }
     });
 
-    // Initialize components
+    // Initialize components. `position_store` and `session_tracker` are
+    // shared across the file-event, idle-session, and position-save
+    // workers below, the same way the old single-threaded loop mutated
+    // them from both its file-event and idle-check branches.
     let mut file_watcher = FileWatcher::new()?;
     let parser = LogParser::new();
-    let mut position_store = PositionStore::new(PositionStore::default_path()?)?;
-    let mut session_tracker = SessionTracker::new();
+    let position_store = Arc::new(Mutex::new(PositionStore::new(PositionStore::default_path()?)?));
+    let session_tracker = Arc::new(Mutex::new(SessionTracker::new()));
     let ipc_client = IpcClient::default();
 
     // Check if Python service is running
@@ -49,41 +63,152 @@ pub async fn run_daemon() -> Result<(), Error> {
     file_watcher.start()?;
     info!("Watching for Claude Code log changes");
 
-    // Track last idle check time
-    let mut last_idle_check = std::time::Instant::now();
-
-    // Main event loop
-    loop {
-        // Poll for file events
-        while let Some(event) = file_watcher.try_recv() {
-            match event {
-                WatchEvent::Modified(path) | WatchEvent::Created(path) => {
-                    if let Err(e) =
-                        process_file(&path, &parser, &mut position_store, &mut session_tracker)
-                    {
-                        error!(path = %path.display(), error = %e, "Failed to process file");
-                    }
-                }
-            }
-        }
+    // Control channel: lets `sqrl daemon pause`/`resume` reach this
+    // process over IPC without killing it, and a `SetTranquility`
+    // command live-adjust the scrub worker below. `sqrl config
+    // daemon.scrub_tranquility` only saves the config file, which is
+    // read here once at startup.
+    // `pause_flag`/`tranquility` are shared with the workers; `owner_loop`
+    // is the sole writer of persisted state, so every command goes
+    // through one place.
+    let initial_tranquility = Config::load().map(|c| c.daemon.scrub_tranquility).unwrap_or(5);
+    let (controller, pause_flag, tranquility, control_rx) = Controller::new(initial_tranquility);
+    tokio::spawn(control::owner_loop(pause_flag.clone(), tranquility.clone(), control_rx));
+    ipc_client.register_controller(controller).await;
 
-        // Check if it's time for idle session check
-        if last_idle_check.elapsed() >= Duration::from_secs(IDLE_CHECK_INTERVAL_SECS) {
-            last_idle_check = std::time::Instant::now();
+    let mut manager = WorkerManager::new();
+    manager.spawn(Box::new(FileEventWorker {
+        file_watcher,
+        parser,
+        position_store: position_store.clone(),
+        session_tracker: session_tracker.clone(),
+    }));
+    manager.spawn(Box::new(IdleSessionWorker {
+        session_tracker,
+        ipc_client,
+        pause_flag,
+        pending: Vec::new(),
+    }));
+    manager.spawn(Box::new(PositionSaveWorker { position_store }));
+    manager.spawn(Box::new(ScrubWorker::new(tranquility)));
 
-            let completed = session_tracker.check_idle_sessions();
-            for session in completed {
-                send_to_service(&ipc_client, session).await;
-            }
+    manager.join().await;
+    Ok(())
+}
+
+/// Send a `Pause` command to the running daemon over IPC. While paused,
+/// the file-event worker keeps draining events and saving positions, and
+/// the idle-session worker keeps tracking sessions to completion — it
+/// just buffers them instead of submitting to the Python service, and
+/// flushes that buffer on its first idle check after `resume` (at most
+/// `IDLE_CHECK_INTERVAL_SECS` later). Nothing is lost.
+pub async fn pause() -> Result<(), Error> {
+    IpcClient::default().send_control_command(Command::Pause).await
+}
+
+/// Send a `Resume` command to the running daemon over IPC.
+pub async fn resume() -> Result<(), Error> {
+    IpcClient::default().send_control_command(Command::Resume).await
+}
+
+/// Print whether the running daemon is currently paused or running.
+pub async fn status() -> Result<(), Error> {
+    let state = IpcClient::default().daemon_run_state().await?;
+    match state {
+        control::RunState::Running => println!("running"),
+        control::RunState::Paused => println!("paused (memory extraction is suspended)"),
+    }
+    Ok(())
+}
+
+/// Drains debounced file events and feeds them through the log parser,
+/// session tracker, and position store. Replaces the old loop's
+/// `try_recv_debounced` drain.
+struct FileEventWorker {
+    file_watcher: FileWatcher,
+    parser: LogParser,
+    position_store: Arc<Mutex<PositionStore>>,
+    session_tracker: Arc<Mutex<SessionTracker>>,
+}
+
+#[async_trait::async_trait]
+impl Worker for FileEventWorker {
+    fn name(&self) -> &str {
+        "file-events"
+    }
+
+    async fn work(&mut self) -> Result<WorkerState, Error> {
+        let Some(event) = self.file_watcher.try_recv_debounced() else {
+            return Ok(WorkerState::Idle(Some(Duration::from_millis(POLL_INTERVAL_MS))));
+        };
 
-            // Periodically save position store
-            if let Err(e) = position_store.save() {
-                error!(error = %e, "Failed to save position store");
+        let (WatchEvent::Modified(path) | WatchEvent::Created(path)) = event;
+
+        let mut position_store = self.position_store.lock().await;
+        let mut session_tracker = self.session_tracker.lock().await;
+        process_file(&path, &self.parser, &mut position_store, &mut session_tracker)?;
+        Ok(WorkerState::Busy)
+    }
+}
+
+/// Periodically checks for idle sessions and forwards completed ones to
+/// the Python Memory Service. Replaces the old loop's idle-check branch.
+/// While `pause_flag` is set, completed sessions are still drained from
+/// the tracker (so they don't pile up there) but are buffered in
+/// `pending` instead of sent — `check_idle_sessions` has already
+/// advanced the `PositionStore` offset past them, so dropping them here
+/// would lose their corrections/memories for good. Everything in
+/// `pending` is flushed to the service on the first idle check after
+/// `sqrl daemon resume` clears the flag.
+struct IdleSessionWorker {
+    session_tracker: Arc<Mutex<SessionTracker>>,
+    ipc_client: IpcClient,
+    pause_flag: PauseFlag,
+    pending: Vec<CompletedSession>,
+}
+
+#[async_trait::async_trait]
+impl Worker for IdleSessionWorker {
+    fn name(&self) -> &str {
+        "idle-sessions"
+    }
+
+    async fn work(&mut self) -> Result<WorkerState, Error> {
+        let completed = self.session_tracker.lock().await.check_idle_sessions();
+
+        if self.pause_flag.is_paused() {
+            if !completed.is_empty() {
+                info!(count = completed.len(), "Paused: buffering completed sessions for resume");
+            }
+            self.pending.extend(completed);
+        } else {
+            if !self.pending.is_empty() {
+                info!(count = self.pending.len(), "Resumed: flushing buffered completed sessions");
+            }
+            for session in self.pending.drain(..).chain(completed) {
+                send_to_service(&self.ipc_client, session).await;
             }
         }
+        Ok(WorkerState::Idle(Some(Duration::from_secs(IDLE_CHECK_INTERVAL_SECS))))
+    }
+}
+
+/// Periodically flushes the `PositionStore` to disk. Replaces the old
+/// loop's position-save call, previously piggybacked on the idle-check
+/// branch.
+struct PositionSaveWorker {
+    position_store: Arc<Mutex<PositionStore>>,
+}
+
+#[async_trait::async_trait]
+impl Worker for PositionSaveWorker {
+    fn name(&self) -> &str {
+        "position-save"
+    }
 
-        // Small sleep to avoid busy-waiting
-        sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+    async fn work(&mut self) -> Result<WorkerState, Error> {
+        self.position_store.lock().await.save()?;
+        Ok(WorkerState::Idle(Some(Duration::from_secs(IDLE_CHECK_INTERVAL_SECS))))
     }
 }
 
@@ -118,8 +243,10 @@ fn process_file(
     Ok(())
 }
 
-/// Send a completed session to the Python service.
-async fn send_to_service(client: &IpcClient, session: CompletedSession) {
+/// Send a completed session to the Python service. `pub(crate)` so
+/// `cli::backfill` can submit historical sessions through the exact same
+/// path as the live daemon instead of duplicating it.
+pub(crate) async fn send_to_service(client: &IpcClient, session: CompletedSession) {
     if session.events.is_empty() {
         return;
     }
@@ -0,0 +1,213 @@
+//! Generic supervised background-worker framework (adapted from Garage's
+//! background-task-manager design), used by `watch::run_daemon` to turn
+//! each of its inline concerns into an independently supervised unit with
+//! visibility into its health — see `sqrl workers`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::error::Error;
+
+/// What a worker did on its last tick.
+pub enum WorkerState {
+    /// Did useful work; the manager polls it again immediately.
+    Busy,
+    /// Nothing to do right now; the manager sleeps this long (or a
+    /// default interval, if `None`) before polling again.
+    Idle(Option<Duration>),
+    /// Permanently finished; the manager stops polling it.
+    Done,
+}
+
+/// One supervised unit of background work.
+#[async_trait]
+pub trait Worker: Send + 'static {
+    /// Name shown in `sqrl workers` output.
+    fn name(&self) -> &str;
+
+    /// Do one unit of work and report what happened.
+    async fn work(&mut self) -> Result<WorkerState, Error>;
+
+    /// Optional free-form progress string shown by `sqrl workers`
+    /// alongside this worker's run state (e.g. scan position, percent
+    /// complete). Most workers have nothing more specific to say than
+    /// their `RunState` already conveys.
+    fn progress(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A worker's run state, as shown by `sqrl workers`.
+#[derive(Debug, Clone)]
+pub enum RunState {
+    /// Currently busy, or was on its last tick.
+    Busy,
+    /// Waiting for its next poll, `next_poll_in` from now.
+    Idle { next_poll_in: Option<Duration> },
+    /// Stopped after too many consecutive errors.
+    Dead,
+}
+
+/// Point-in-time status of one worker, tracked by [`WorkerManager`].
+#[derive(Debug, Clone)]
+pub struct WorkerStatus {
+    pub name: String,
+    pub state: RunState,
+    pub error_count: u32,
+    pub last_error: Option<(String, SystemTime)>,
+    /// This worker's own [`Worker::progress`] string, if it reported one
+    /// on its last tick.
+    pub detail: Option<String>,
+}
+
+/// A worker is declared Dead (and its task exits) after this many
+/// consecutive `Err` returns from `work()`.
+const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+
+/// Poll interval used for `WorkerState::Idle(None)`, when a worker doesn't
+/// have a more specific opinion on when it should next run.
+const DEFAULT_IDLE_POLL: Duration = Duration::from_secs(300);
+
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Spawns each registered [`Worker`] on its own tokio task, and keeps a
+/// shared snapshot of its last state, running error count, and last error
+/// so operators aren't flying blind when memories stop being extracted.
+pub struct WorkerManager {
+    statuses: Arc<Mutex<HashMap<String, WorkerStatus>>>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self {
+            statuses: Arc::new(Mutex::new(HashMap::new())),
+            handles: Vec::new(),
+        }
+    }
+
+    /// Spawn `worker` as its own supervised task. Runs until it returns
+    /// `Done`, or is restarted after a backoff when it returns `Err` —
+    /// up to [`MAX_CONSECUTIVE_ERRORS`] in a row, after which it's marked
+    /// Dead and its task exits instead of retrying forever.
+    pub fn spawn(&mut self, mut worker: Box<dyn Worker>) {
+        let statuses = self.statuses.clone();
+        let name = worker.name().to_string();
+
+        let handle = tokio::spawn(async move {
+            {
+                let mut map = statuses.lock().await;
+                map.insert(
+                    name.clone(),
+                    WorkerStatus {
+                        name: name.clone(),
+                        state: RunState::Busy,
+                        error_count: 0,
+                        last_error: None,
+                        detail: None,
+                    },
+                );
+            }
+
+            let mut consecutive_errors: u32 = 0;
+
+            loop {
+                let result = worker.work().await;
+                let detail = worker.progress();
+                let mut map = statuses.lock().await;
+                let status = map.entry(name.clone()).or_insert_with(|| WorkerStatus {
+                    name: name.clone(),
+                    state: RunState::Busy,
+                    error_count: 0,
+                    last_error: None,
+                    detail: None,
+                });
+                status.detail = detail;
+
+                match result {
+                    Ok(WorkerState::Busy) => {
+                        consecutive_errors = 0;
+                        status.error_count = 0;
+                        status.state = RunState::Busy;
+                        drop(map);
+                    }
+                    Ok(WorkerState::Idle(sleep_for)) => {
+                        consecutive_errors = 0;
+                        status.error_count = 0;
+                        let sleep_for = sleep_for.unwrap_or(DEFAULT_IDLE_POLL);
+                        status.state = RunState::Idle { next_poll_in: Some(sleep_for) };
+                        drop(map);
+                        tokio::time::sleep(sleep_for).await;
+                    }
+                    Ok(WorkerState::Done) => {
+                        status.state = RunState::Idle { next_poll_in: None };
+                        drop(map);
+                        tracing::info!("Worker '{}' finished", name);
+                        break;
+                    }
+                    Err(e) => {
+                        consecutive_errors += 1;
+                        status.error_count = consecutive_errors;
+                        status.last_error = Some((e.to_string(), SystemTime::now()));
+
+                        if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                            status.state = RunState::Dead;
+                            drop(map);
+                            tracing::error!(
+                                "Worker '{}' dead after {} consecutive errors: {}",
+                                name,
+                                consecutive_errors,
+                                e
+                            );
+                            break;
+                        }
+
+                        let backoff =
+                            (BASE_BACKOFF * 2u32.pow(consecutive_errors.saturating_sub(1))).min(MAX_BACKOFF);
+                        status.state = RunState::Idle { next_poll_in: Some(backoff) };
+                        drop(map);
+                        tracing::warn!(
+                            "Worker '{}' errored, retrying in {:?}: {}",
+                            name,
+                            backoff,
+                            e
+                        );
+                        tokio::time::sleep(backoff).await;
+                    }
+                }
+            }
+        });
+
+        self.handles.push(handle);
+    }
+
+    /// Snapshot of every registered worker's current status, sorted by
+    /// name, for `sqrl workers` to render.
+    pub async fn statuses(&self) -> Vec<WorkerStatus> {
+        let map = self.statuses.lock().await;
+        let mut statuses: Vec<_> = map.values().cloned().collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// Block until every spawned worker task exits. `run_daemon` awaits
+    /// this in place of its old hand-rolled loop; it only returns once
+    /// every worker has gone Dead or Done.
+    pub async fn join(self) {
+        for handle in self.handles {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Default for WorkerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,39 @@
+//! `sqrl workers` — report each background worker's health.
+
+use crate::cli::worker::RunState;
+use crate::error::Error;
+use crate::ipc::IpcClient;
+
+/// Run workers command. Queries the daemon's worker statuses over the
+/// existing IPC channel (the same `IpcClient` `watch::run_daemon` uses to
+/// reach the Python Memory Service) and prints each worker's name and
+/// state — Active (Busy), Idle (with time until its next run), or Dead
+/// (too many consecutive errors) — plus its last error, if any.
+pub async fn run() -> Result<(), Error> {
+    let client = IpcClient::default();
+    let statuses = client.get_worker_statuses().await?;
+
+    if statuses.is_empty() {
+        println!("No workers reported (is the daemon running?)");
+        return Ok(());
+    }
+
+    for status in statuses {
+        let state = match status.state {
+            RunState::Busy => "Active".to_string(),
+            RunState::Idle { next_poll_in: Some(d) } => format!("Idle (next run in {:?})", d),
+            RunState::Idle { next_poll_in: None } => "Idle".to_string(),
+            RunState::Dead => format!("Dead (after {} consecutive errors)", status.error_count),
+        };
+
+        println!("{}: {}", status.name, state);
+        if let Some(detail) = status.detail {
+            println!("  {}", detail);
+        }
+        if let Some((message, _)) = status.last_error {
+            println!("  last error: {}", message);
+        }
+    }
+
+    Ok(())
+}
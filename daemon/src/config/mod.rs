@@ -27,6 +27,22 @@ pub struct Config {
     /// Internal state (not user-editable).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub internal: Option<InternalConfig>,
+
+    /// Subprojects in a monorepo, each owning its own docs.
+    /// See `storage::ChangeRouter` for how changed files are attributed.
+    #[serde(default)]
+    pub subprojects: Vec<SubprojectConfig>,
+
+    /// Names of extensions to enable, matched against the `name()` each
+    /// `extensions::McpToolExtension` / `extensions::DebtCheckExtension`
+    /// reports. Empty by default — Squirrel ships with no built-in
+    /// extensions, so this only matters once some are registered.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+
+    /// SQLite connection tuning. See `storage::open_tuned`.
+    #[serde(default)]
+    pub storage: StorageConfig,
 }
 
 /// AI tools configuration.
@@ -62,6 +78,37 @@ pub struct HooksConfig {
     /// Auto-install hooks when git detected.
     #[serde(default = "default_true")]
     pub auto_install: bool,
+
+    /// Hook types to render and install, e.g. `["pre-push", "commit-msg"]`.
+    /// See `cli::hooks::HOOK_TYPES` for the supported set.
+    #[serde(default = "default_enabled_hooks")]
+    pub enabled: Vec<String>,
+}
+
+/// SQLite connection tuning, applied by `storage::open_tuned` to every
+/// new connection opened against `.sqrl/memory.db`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// How long (in milliseconds) a connection retries before giving up
+    /// with `SQLITE_BUSY` when another connection holds the write lock.
+    #[serde(default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+}
+
+/// A subproject in a monorepo, with its own doc ownership.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubprojectConfig {
+    /// Unique name for this subproject (used in doc-debt reports).
+    pub name: String,
+
+    /// Path prefix owned by this subproject, relative to the repo root
+    /// (e.g. `"services/auth/"`). Longer roots take priority over shorter
+    /// ones that also match, so nested subprojects resolve to the deepest.
+    pub root: String,
+
+    /// Doc include/exclude paths for this subproject only.
+    #[serde(default)]
+    pub docs: DocsConfig,
 }
 
 /// Internal state (managed by sqrl, not user).
@@ -70,6 +117,112 @@ pub struct InternalConfig {
     pub initialized_at: String,
 }
 
+/// A single project Squirrel has been initialized in, tracked in the
+/// global `ProjectsRegistry`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    pub project_id: String,
+    pub root_path: PathBuf,
+    pub initialized_at: String,
+
+    /// User-defined tags (e.g. `"backend"`, `"client"`) used to target a
+    /// subset of registered projects from cross-project commands like
+    /// `sync`/`status`. See `ProjectsRegistry::filter_by_tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Registry of every project Squirrel has been initialized in, stored at
+/// `~/.sqrl/projects.yaml`. Lets cross-project commands target a subset of
+/// registered projects by tag instead of always operating on all of them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectsRegistry {
+    #[serde(default)]
+    pub projects: Vec<ProjectConfig>,
+}
+
+impl ProjectsRegistry {
+    /// Get the registry file path.
+    pub fn path() -> Result<PathBuf, Error> {
+        let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+        Ok(home.join(".sqrl").join("projects.yaml"))
+    }
+
+    /// Load the registry, or an empty one if it hasn't been created yet.
+    pub fn load() -> Result<Self, Error> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        let registry: ProjectsRegistry =
+            serde_yaml::from_str(&content).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        Ok(registry)
+    }
+
+    /// Save the registry.
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_yaml::to_string(self).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        let with_header = format!("# Squirrel registered projects\n\n{}", content);
+        fs::write(&path, with_header)?;
+        Ok(())
+    }
+
+    /// Register a project, replacing any existing entry with the same
+    /// `project_id`.
+    pub fn register(&mut self, project: ProjectConfig) {
+        self.projects.retain(|p| p.project_id != project.project_id);
+        self.projects.push(project);
+    }
+
+    /// Add a tag to a registered project. Returns `false` if the project
+    /// isn't registered.
+    pub fn add_tag(&mut self, project_id: &str, tag: &str) -> bool {
+        let Some(project) = self
+            .projects
+            .iter_mut()
+            .find(|p| p.project_id == project_id)
+        else {
+            return false;
+        };
+        if !project.tags.iter().any(|t| t == tag) {
+            project.tags.push(tag.to_string());
+        }
+        true
+    }
+
+    /// Remove a tag from a registered project. Returns `false` if the
+    /// project isn't registered.
+    pub fn remove_tag(&mut self, project_id: &str, tag: &str) -> bool {
+        let Some(project) = self
+            .projects
+            .iter_mut()
+            .find(|p| p.project_id == project_id)
+        else {
+            return false;
+        };
+        project.tags.retain(|t| t != tag);
+        true
+    }
+
+    /// Registered projects carrying the given tag. `None` selects every
+    /// registered project (used when no `--tag` filter was passed).
+    pub fn filter_by_tag<'a>(&'a self, tag: Option<&str>) -> Vec<&'a ProjectConfig> {
+        match tag {
+            Some(tag) => self
+                .projects
+                .iter()
+                .filter(|p| p.tags.iter().any(|t| t == tag))
+                .collect(),
+            None => self.projects.iter().collect(),
+        }
+    }
+}
+
 // Default value functions
 fn default_true() -> bool {
     true
@@ -93,6 +246,14 @@ fn default_include_paths() -> Vec<String> {
     ]
 }
 
+fn default_enabled_hooks() -> Vec<String> {
+    vec!["pre-push".to_string()]
+}
+
+fn default_busy_timeout_ms() -> u64 {
+    5_000
+}
+
 fn default_exclude_paths() -> Vec<String> {
     vec![
         "node_modules/".to_string(),
@@ -125,7 +286,18 @@ impl Default for DocsConfig {
 
 impl Default for HooksConfig {
     fn default() -> Self {
-        Self { auto_install: true }
+        Self {
+            auto_install: true,
+            enabled: default_enabled_hooks(),
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: default_busy_timeout_ms(),
+        }
     }
 }
 
@@ -138,6 +310,9 @@ impl Default for Config {
             internal: Some(InternalConfig {
                 initialized_at: chrono::Utc::now().to_rfc3339(),
             }),
+            subprojects: Vec::new(),
+            extensions: Vec::new(),
+            storage: StorageConfig::default(),
         }
     }
 }
@@ -185,6 +360,38 @@ mod tests {
         assert!(!config.tools.cursor);
         assert_eq!(config.docs.extensions, vec!["md", "mdc", "txt", "rst"]);
         assert!(config.hooks.auto_install);
+        assert_eq!(config.hooks.enabled, vec!["pre-push"]);
+    }
+
+    #[test]
+    fn test_registry_tag_filter() {
+        let mut registry = ProjectsRegistry::default();
+        registry.register(ProjectConfig {
+            project_id: "api".to_string(),
+            root_path: PathBuf::from("/repos/api"),
+            initialized_at: "2026-01-01T00:00:00Z".to_string(),
+            tags: vec![],
+        });
+        registry.register(ProjectConfig {
+            project_id: "worker".to_string(),
+            root_path: PathBuf::from("/repos/worker"),
+            initialized_at: "2026-01-01T00:00:00Z".to_string(),
+            tags: vec![],
+        });
+
+        assert!(registry.add_tag("api", "backend"));
+        assert!(registry.add_tag("worker", "backend"));
+        assert!(!registry.add_tag("missing", "backend"));
+
+        let backend = registry.filter_by_tag(Some("backend"));
+        assert_eq!(backend.len(), 2);
+
+        assert!(registry.remove_tag("worker", "backend"));
+        let backend = registry.filter_by_tag(Some("backend"));
+        assert_eq!(backend.len(), 1);
+        assert_eq!(backend[0].project_id, "api");
+
+        assert_eq!(registry.filter_by_tag(None).len(), 2);
     }
 
     #[test]
@@ -91,10 +91,10 @@ async fn delete_style(Path(id): Path<String>) -> StatusCode {
 // === Projects ===
 
 #[derive(Serialize)]
-struct ProjectResponse {
-    id: String,
-    path: String,
-    memory_count: usize,
+pub(crate) struct ProjectResponse {
+    pub(crate) id: String,
+    pub(crate) path: String,
+    pub(crate) memory_count: usize,
 }
 
 async fn list_projects() -> Json<Vec<ProjectResponse>> {
@@ -103,7 +103,7 @@ async fn list_projects() -> Json<Vec<ProjectResponse>> {
 }
 
 /// Discover projects by scanning common locations for .sqrl directories.
-fn discover_projects() -> Vec<ProjectResponse> {
+pub(crate) fn discover_projects() -> Vec<ProjectResponse> {
     let mut projects = Vec::new();
 
     // Check current directory
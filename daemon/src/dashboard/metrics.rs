@@ -0,0 +1,122 @@
+//! Prometheus text-format metrics for the dashboard (`GET /metrics`).
+//!
+//! Scrapes storage fresh on every request rather than caching, the same
+//! tradeoff most admin metrics modules make for a low-traffic endpoint.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use axum::http::header;
+use axum::response::IntoResponse;
+
+use crate::cli::service;
+use crate::mcp::metrics as mcp_metrics;
+use crate::storage;
+
+use super::api;
+
+/// Axum handler for `GET /metrics`.
+pub async fn handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render(),
+    )
+}
+
+/// Render the current metrics snapshot in Prometheus text exposition format.
+fn render() -> String {
+    let mut out = String::new();
+    let projects = api::discover_projects();
+
+    writeln!(out, "# HELP squirrel_projects_discovered Number of Squirrel projects discovered.").ok();
+    writeln!(out, "# TYPE squirrel_projects_discovered gauge").ok();
+    writeln!(out, "squirrel_projects_discovered {}", projects.len()).ok();
+
+    writeln!(
+        out,
+        "# HELP squirrel_daemon_running Whether the background daemon is running (1) or not (0)."
+    )
+    .ok();
+    writeln!(out, "# TYPE squirrel_daemon_running gauge").ok();
+    writeln!(
+        out,
+        "squirrel_daemon_running {}",
+        service::is_running().unwrap_or(false) as u8
+    )
+    .ok();
+
+    writeln!(out, "# HELP squirrel_memories_total Total memories stored, per project.").ok();
+    writeln!(out, "# TYPE squirrel_memories_total gauge").ok();
+    for project in &projects {
+        writeln!(
+            out,
+            "squirrel_memories_total{{project=\"{}\"}} {}",
+            project.id, project.memory_count
+        )
+        .ok();
+    }
+
+    // Per-kind / per-tier counts and aggregate use_count, scraped per
+    // project. This dashboard's memory schema calls them
+    // category/subcategory rather than kind/tier, so that's what's grouped
+    // under the `kind`/`tier` metric labels below.
+    let mut kind_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut tier_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut use_count_total: i64 = 0;
+
+    for project in &projects {
+        let path = Path::new(&project.path);
+        for memory in storage::get_project_memories(path).unwrap_or_default() {
+            *kind_counts.entry(memory.category.clone()).or_insert(0) += 1;
+            *tier_counts.entry(memory.subcategory.clone()).or_insert(0) += 1;
+            use_count_total += memory.use_count;
+        }
+    }
+
+    writeln!(
+        out,
+        "# HELP squirrel_memories_by_kind_total Memories per kind, across all discovered projects."
+    )
+    .ok();
+    writeln!(out, "# TYPE squirrel_memories_by_kind_total gauge").ok();
+    for (kind, count) in &kind_counts {
+        writeln!(out, "squirrel_memories_by_kind_total{{kind=\"{}\"}} {}", kind, count).ok();
+    }
+
+    writeln!(
+        out,
+        "# HELP squirrel_memories_by_tier_total Memories per tier, across all discovered projects."
+    )
+    .ok();
+    writeln!(out, "# TYPE squirrel_memories_by_tier_total gauge").ok();
+    for (tier, count) in &tier_counts {
+        writeln!(out, "squirrel_memories_by_tier_total{{tier=\"{}\"}} {}", tier, count).ok();
+    }
+
+    writeln!(
+        out,
+        "# HELP squirrel_memory_use_count_total Aggregate use_count across all discovered projects' memories."
+    )
+    .ok();
+    writeln!(out, "# TYPE squirrel_memory_use_count_total counter").ok();
+    writeln!(out, "squirrel_memory_use_count_total {}", use_count_total).ok();
+
+    writeln!(
+        out,
+        "# HELP squirrel_mcp_tool_calls_total MCP tools/call invocations, by tool and outcome."
+    )
+    .ok();
+    writeln!(out, "# TYPE squirrel_mcp_tool_calls_total counter").ok();
+    for (tool, success, count) in mcp_metrics::snapshot() {
+        let outcome = if success { "success" } else { "error" };
+        writeln!(
+            out,
+            "squirrel_mcp_tool_calls_total{{tool=\"{}\",outcome=\"{}\"}} {}",
+            tool, outcome, count
+        )
+        .ok();
+    }
+
+    out
+}
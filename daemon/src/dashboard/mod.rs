@@ -3,6 +3,7 @@
 //! Serves the configuration UI at http://localhost:9741
 
 mod api;
+mod metrics;
 
 use std::net::SocketAddr;
 
@@ -42,6 +43,7 @@ fn create_router() -> Router {
 
     Router::new()
         .nest("/api", api::routes())
+        .route("/metrics", axum::routing::get(metrics::handler))
         .fallback(serve_index)
         .layer(cors)
 }
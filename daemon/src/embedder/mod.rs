@@ -0,0 +1,122 @@
+//! Pluggable text embedder for semantic memory search (see `cli::search`).
+//!
+//! `Config`'s `llm.embedding_model` setting selects the embedder: an
+//! `http(s)://` URL resolves to an HTTP endpoint. No local model runtime
+//! is vendored in this build, so a bare model name (e.g. `all-MiniLM-L6-v2`)
+//! resolves to no embedder at all rather than one that's guaranteed to
+//! fail `embed()` — callers already treat `from_config` returning `None`
+//! as "degrade to lexical search / skip re-embedding", so this keeps
+//! that the only way an unconfigured/unsupported setup shows up, instead
+//! of also needing to handle an `embed()` that always errors.
+//! Vectors come back L2-normalized so callers can rank by plain dot
+//! product (cosine similarity) instead of re-normalizing on every compare.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::Error;
+
+/// A configured text embedder.
+pub trait Embedder {
+    /// Embed `text`, returning an L2-normalized vector.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, Error>;
+}
+
+/// Resolve the embedder configured in `config.llm.embedding_model`, if any.
+/// Returns `None` when no embedding model is configured, or when it names
+/// a local model this build can't run, so callers can degrade gracefully
+/// to lexical search / skip re-embedding instead of getting an `Embedder`
+/// that's certain to error on every call.
+pub fn from_config(config: &Config) -> Option<Box<dyn Embedder>> {
+    let model = config.llm.embedding_model.trim();
+    if model.is_empty() {
+        return None;
+    }
+
+    if model.starts_with("http://") || model.starts_with("https://") {
+        Some(Box::new(HttpEmbedder {
+            endpoint: model.to_string(),
+        }))
+    } else {
+        tracing::warn!(
+            model = model,
+            "local embedding models aren't supported in this build; \
+             set llm.embedding_model to an http(s):// endpoint instead"
+        );
+        None
+    }
+}
+
+/// Embeds by POSTing to an HTTP endpoint that speaks the common
+/// `{"input": "..."} -> {"embedding": [...]}` convention.
+struct HttpEmbedder {
+    endpoint: String,
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embedding: Vec<f32>,
+}
+
+impl Embedder for HttpEmbedder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, Error> {
+        let response = reqwest::blocking::Client::new()
+            .post(&self.endpoint)
+            .json(&EmbedRequest { input: text })
+            .send()
+            .map_err(|e| Error::Embedding(format!("embedding request failed: {e}")))?
+            .error_for_status()
+            .map_err(|e| Error::Embedding(format!("embedding endpoint returned an error: {e}")))?
+            .json::<EmbedResponse>()
+            .map_err(|e| Error::Embedding(format!("invalid embedding response: {e}")))?;
+
+        let mut vector = response.embedding;
+        normalize(&mut vector);
+        Ok(vector)
+    }
+}
+
+/// L2-normalize `v` in place. A zero vector is left as-is.
+pub fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Cosine similarity between two L2-normalized vectors, i.e. a plain dot
+/// product. Vectors of mismatched length are treated as dissimilar (0.0)
+/// rather than panicking, since the embedding model behind a row can
+/// change over time.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Pack a vector into a little-endian `f32` blob, for storage in a SQLite
+/// `BLOB` column.
+pub fn pack(v: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(v.len() * 4);
+    for x in v {
+        bytes.extend_from_slice(&x.to_le_bytes());
+    }
+    bytes
+}
+
+/// Unpack a little-endian `f32` blob back into a vector. Trailing bytes
+/// that don't form a full `f32` are ignored.
+pub fn unpack(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
@@ -25,4 +25,16 @@ pub enum Error {
 
     #[error("Config parse error: {0}")]
     ConfigParse(String),
+
+    #[error("Hook error: {0}")]
+    Hooks(String),
+
+    #[error("Embedding error: {0}")]
+    Embedding(String),
+
+    #[error("Watcher error: {0}")]
+    Watcher(String),
+
+    #[error("Metrics error: {0}")]
+    Metrics(String),
 }
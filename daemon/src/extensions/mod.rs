@@ -0,0 +1,118 @@
+//! Extension registry for third-party MCP tools and docguard checks.
+//!
+//! Borrows the "support multiple extensions consistently" shape jj uses for
+//! its own extension points: a trait per extension kind, a registry that
+//! holds a `Vec` of implementations (not a single slot), and dispatch that
+//! runs all registered implementations rather than picking one. The `Vec`
+//! shape is deliberate so a future dynamic-loading ABI can register many
+//! extensions discovered from `Config::extensions` without changing this API.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// A third-party MCP tool: advertised in `tools/list`, dispatched from
+/// `tools/call` when its `name()` matches.
+pub trait McpToolExtension: Send + Sync {
+    /// Tool name, as advertised to MCP clients (must be unique).
+    fn name(&self) -> &str;
+
+    /// Short human-readable description, shown in `tools/list`.
+    fn description(&self) -> &str;
+
+    /// JSON Schema for the tool's `arguments`.
+    fn schema(&self) -> Value;
+
+    /// Handle a `tools/call` invocation, given the call's `arguments`.
+    fn handle(&self, arguments: &Value) -> Result<Value, Error>;
+}
+
+/// A finding from a `DebtCheckExtension` run against one commit/push.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DebtFinding {
+    /// Which check produced this finding.
+    pub check_name: String,
+    /// Human-readable description of the debt.
+    pub message: String,
+}
+
+/// A third-party pre-push/commit check: inspects the files changed in a
+/// commit/push range and returns findings (debt) it detected.
+pub trait DebtCheckExtension: Send + Sync {
+    /// Check name, used to label its findings and in config/log output.
+    fn name(&self) -> &str;
+
+    /// Inspect the changed files and return any findings.
+    fn check(&self, project_root: &Path, changed_files: &[String]) -> Result<Vec<DebtFinding>, Error>;
+}
+
+/// Collects registered extensions of both kinds and dispatches to all of
+/// them. Construct via `ExtensionRegistry::new` and `register_*`, or
+/// `ExtensionRegistry::built_in()` for the (currently empty) default set.
+#[derive(Default)]
+pub struct ExtensionRegistry {
+    mcp_tools: Vec<Box<dyn McpToolExtension>>,
+    debt_checks: Vec<Box<dyn DebtCheckExtension>>,
+}
+
+impl ExtensionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry Squirrel ships with by default. Empty today; extensions
+    /// are opted into via `Config::extensions` (see `cli::internal` and
+    /// `mcp::run` for where they'd be instantiated and registered).
+    pub fn built_in() -> Self {
+        Self::new()
+    }
+
+    /// Register an MCP tool extension.
+    pub fn register_mcp_tool(&mut self, ext: Box<dyn McpToolExtension>) {
+        self.mcp_tools.push(ext);
+    }
+
+    /// Register a debt-check extension.
+    pub fn register_debt_check(&mut self, ext: Box<dyn DebtCheckExtension>) {
+        self.debt_checks.push(ext);
+    }
+
+    /// All registered MCP tools.
+    pub fn mcp_tools(&self) -> &[Box<dyn McpToolExtension>] {
+        &self.mcp_tools
+    }
+
+    /// Find a registered MCP tool by name.
+    pub fn find_mcp_tool(&self, name: &str) -> Option<&dyn McpToolExtension> {
+        self.mcp_tools
+            .iter()
+            .find(|ext| ext.name() == name)
+            .map(|ext| ext.as_ref())
+    }
+
+    /// Run every registered debt check against the changed files, collecting
+    /// findings from all of them (a failing check doesn't stop the others).
+    pub fn run_debt_checks(
+        &self,
+        project_root: &Path,
+        changed_files: &[String],
+    ) -> Vec<DebtFinding> {
+        let mut findings = Vec::new();
+        for check in &self.debt_checks {
+            match check.check(project_root, changed_files) {
+                Ok(found) => findings.extend(found),
+                Err(e) => {
+                    tracing::warn!(
+                        check = check.name(),
+                        error = %e,
+                        "Debt check extension failed"
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
@@ -0,0 +1,174 @@
+//! Gitignore-style ignore rules, shared by doc-file discovery
+//! (`cli::internal::find_doc_files`) and the file watcher.
+//!
+//! Parses `.gitignore`, `.git/info/exclude`, and an optional `.sqrlignore`
+//! (in that order, so later files can override earlier ones, matching
+//! git's own precedence), plus a project's configured `docs.exclude_paths`
+//! as further rules. `IgnoreSet::is_ignored` applies them with
+//! last-match-wins semantics, same as `git check-ignore`.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::DocsConfig;
+
+/// A single gitignore-style rule, already relative to the ignore file it
+/// came from.
+#[derive(Debug, Clone)]
+struct Rule {
+    /// The glob pattern to match against a path relative to `base`, with
+    /// the leading `!` and trailing `/` already stripped.
+    pattern: glob::Pattern,
+    /// `!`-prefixed: a match un-ignores instead of ignoring.
+    negated: bool,
+    /// Trailing-`/`: only matches directories.
+    dir_only: bool,
+    /// Whether the pattern contains a `/` other than a trailing one, which
+    /// in gitignore semantics anchors it to `base` instead of letting it
+    /// match at any depth.
+    anchored: bool,
+    /// Directory the pattern is relative to (the ignore file's parent, or
+    /// the project root for config-provided exclude paths).
+    base: PathBuf,
+}
+
+impl Rule {
+    /// Parse one non-comment, non-blank gitignore line.
+    fn parse(line: &str, base: &Path) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let negated = line.starts_with('!');
+        let mut pat = if negated { &line[1..] } else { line };
+
+        let dir_only = pat.ends_with('/');
+        if dir_only {
+            pat = &pat[..pat.len() - 1];
+        }
+
+        // A leading `/` anchors explicitly; so does any other internal
+        // `/` (gitignore treats a pattern with a slash anywhere but the
+        // end as anchored to its directory).
+        let anchored = pat.trim_start_matches('/').contains('/') || pat.starts_with('/');
+        let pat = pat.trim_start_matches('/');
+
+        let pattern = glob::Pattern::new(pat).ok()?;
+        Some(Rule {
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+            base: base.to_path_buf(),
+        })
+    }
+
+    /// Whether this rule matches `path` (relative to the project root),
+    /// given whether it names a directory.
+    fn matches(&self, project_root: &Path, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        let Ok(relative_to_base) = path.strip_prefix(
+            self.base
+                .strip_prefix(project_root)
+                .unwrap_or(&self.base),
+        ) else {
+            return false;
+        };
+        let candidate = relative_to_base.to_string_lossy().replace('\\', "/");
+        if candidate.is_empty() {
+            return false;
+        }
+
+        if self.anchored {
+            self.pattern.matches(&candidate)
+        } else {
+            // Unanchored: match at any depth, i.e. as if the pattern were
+            // prefixed with `**/`.
+            let mut rest = candidate.as_str();
+            loop {
+                if self.pattern.matches(rest) {
+                    return true;
+                }
+                match rest.find('/') {
+                    Some(idx) => rest = &rest[idx + 1..],
+                    None => return false,
+                }
+            }
+        }
+    }
+}
+
+/// An ordered set of ignore rules for one project.
+pub struct IgnoreSet {
+    project_root: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl IgnoreSet {
+    /// Build the ignore set for `project_root`: `.gitignore`, then
+    /// `.git/info/exclude`, then `.sqrlignore`, then `docs.exclude_paths`
+    /// from the project's config (each parsed like a `.gitignore` line
+    /// relative to the project root, so an entry such as the default
+    /// `node_modules/` is an unanchored, directory-only rule).
+    pub fn load(project_root: &Path, docs: &DocsConfig) -> Self {
+        let mut rules = Vec::new();
+        rules.extend(Self::parse_file(project_root, &project_root.join(".gitignore")));
+        rules.extend(Self::parse_file(
+            project_root,
+            &project_root.join(".git").join("info").join("exclude"),
+        ));
+        rules.extend(Self::parse_file(project_root, &project_root.join(".sqrlignore")));
+
+        for excluded in &docs.exclude_paths {
+            if let Some(rule) = Rule::parse(excluded, project_root) {
+                rules.push(rule);
+            }
+        }
+
+        Self {
+            project_root: project_root.to_path_buf(),
+            rules,
+        }
+    }
+
+    fn parse_file(project_root: &Path, file: &Path) -> Vec<Rule> {
+        let base = file.parent().unwrap_or(project_root);
+        let Ok(content) = std::fs::read_to_string(file) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| Rule::parse(line, base))
+            .collect()
+    }
+
+    /// Whether `path` (absolute, or relative to the project root) should
+    /// be ignored, applying last-match-wins semantics: the last rule that
+    /// matches decides, so a later `!pattern` can un-ignore an earlier
+    /// match. As in git, a path inside an ignored directory is ignored
+    /// too, whatever the rules say about the path itself.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let path = path
+            .strip_prefix(&self.project_root)
+            .unwrap_or(path)
+            .to_path_buf();
+
+        let last_match_ignores = |candidate: &Path, candidate_is_dir: bool| {
+            let mut ignored = false;
+            for rule in &self.rules {
+                if rule.matches(&self.project_root, candidate, candidate_is_dir) {
+                    ignored = !rule.negated;
+                }
+            }
+            ignored
+        };
+        path.ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| last_match_ignores(dir, true))
+            || last_match_ignores(&path, is_dir)
+    }
+}
@@ -5,12 +5,16 @@
 use std::fs;
 use std::path::PathBuf;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::error::Error;
 
 /// Global configuration stored in `~/.sqrl/config.yaml`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GlobalConfig {
     /// CLI tools enabled (applied to all projects).
     #[serde(default)]
@@ -23,10 +27,14 @@ pub struct GlobalConfig {
     /// Web UI settings.
     #[serde(default)]
     pub ui: UiConfig,
+
+    /// Web API authentication settings.
+    #[serde(default)]
+    pub auth: AuthConfig,
 }
 
 /// CLI tools configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GlobalToolsConfig {
     #[serde(default = "default_true")]
     pub claude_code: bool,
@@ -39,7 +47,7 @@ pub struct GlobalToolsConfig {
 }
 
 /// Web UI settings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UiConfig {
     #[serde(default = "default_port")]
     pub port: u16,
@@ -47,8 +55,57 @@ pub struct UiConfig {
     pub open_browser: bool,
 }
 
+/// Web API authentication (see `web::auth`). Off by default so an
+/// upgraded install keeps working unauthenticated until `enabled` is
+/// set, the same opt-in shape as `storage::vault`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthConfig {
+    /// Whether `require_auth` actually rejects unauthenticated requests.
+    /// Setting a `password` doesn't enable this by itself, so a login
+    /// token can be tested before the rest of the API starts requiring one.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Password checked by `POST /auth/login`. `None` means login is
+    /// refused outright, regardless of `enabled`.
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// HMAC key `login` signs tokens with and `require_auth` verifies
+    /// them against. `#[serde(default = ...)]` only fills this in when the
+    /// field is missing from `config.yaml`; [`GlobalConfig::load`] writes
+    /// that generated value straight back to disk so every subsequent
+    /// `load()` (and thus every `login`/`require_auth` call) sees the same
+    /// key. Never sent back to a client.
+    #[serde(default = "generate_signing_secret")]
+    pub signing_secret: String,
+
+    /// Skip auth entirely for connections from localhost, so a trusted
+    /// local CLI/dashboard doesn't need to log in.
+    #[serde(default = "default_true")]
+    pub allow_loopback: bool,
+}
+
+/// A fresh random 32-byte HMAC key, base64-encoded for storage in YAML.
+fn generate_signing_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    BASE64.encode(bytes)
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            password: None,
+            signing_secret: generate_signing_secret(),
+            allow_loopback: true,
+        }
+    }
+}
+
 /// MCP configuration file (MCP-CONFIG-001).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct McpConfig {
     pub name: String,
     pub command: String,
@@ -98,6 +155,7 @@ impl Default for GlobalConfig {
             tools: GlobalToolsConfig::default(),
             mcps: vec![],
             ui: UiConfig::default(),
+            auth: AuthConfig::default(),
         }
     }
 }
@@ -165,7 +223,10 @@ impl GlobalConfig {
         Self::path().map(|p| p.exists()).unwrap_or(false)
     }
 
-    /// Load global config.
+    /// Load global config. If the file predates `auth.signing_secret` (or
+    /// otherwise omitted it), the freshly-generated default is written back
+    /// immediately so it's stable across calls — `login` and `require_auth`
+    /// each call `load()` independently and must agree on the same key.
     pub fn load() -> Result<Self, Error> {
         let path = Self::path()?;
         if !path.exists() {
@@ -174,6 +235,11 @@ impl GlobalConfig {
         let content = fs::read_to_string(&path)?;
         let config: GlobalConfig =
             serde_yaml::from_str(&content).map_err(|e| Error::ConfigParse(e.to_string()))?;
+
+        if !content.contains("signing_secret") {
+            config.save()?;
+        }
+
         Ok(config)
     }
 
@@ -248,4 +314,13 @@ mod tests {
         assert_eq!(config.ui.port, 3333);
         assert!(config.ui.open_browser);
     }
+
+    #[test]
+    fn test_auth_disabled_by_default_with_a_signing_secret() {
+        let config = GlobalConfig::default();
+        assert!(!config.auth.enabled);
+        assert!(config.auth.password.is_none());
+        assert!(config.auth.allow_loopback);
+        assert!(!config.auth.signing_secret.is_empty());
+    }
 }
@@ -0,0 +1,128 @@
+//! Daemon IPC: a Unix-socket, JSON-line protocol for local clients
+//! (editors, dashboards) to ask the running daemon to `flush` pending
+//! work, or long-poll for memory changes instead of re-querying the whole
+//! database (see `poll_memories`).
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::time::Instant;
+
+use crate::error::Error;
+use crate::storage::{self, Memory};
+
+/// One line of the IPC request protocol.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    Flush,
+    PollMemories {
+        project_root: PathBuf,
+        since_seq: i64,
+        #[serde(default = "default_timeout_ms")]
+        timeout_ms: u64,
+    },
+}
+
+fn default_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Result of a `poll_memories` request: either the memories written since
+/// `since_seq`, or — if `timeout` elapses first — an empty set paired with
+/// the current max `seq`, so the caller can resume from there on its next
+/// poll without missing or re-fetching anything.
+#[derive(Debug, Serialize)]
+pub struct PollResult {
+    pub memories: Vec<Memory>,
+    pub seq: i64,
+}
+
+/// How often `poll_memories` re-checks for new writes while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Bind `socket_path` and serve IPC requests until the task is aborted
+/// (see `cli::daemon::run`, which owns this as a spawned task and removes
+/// the socket file again on shutdown).
+pub async fn run_server(socket_path: &str) -> Result<(), Error> {
+    let path = Path::new(socket_path);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+    tracing::info!("IPC listening on {}", socket_path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream).await {
+                tracing::warn!("IPC connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: UnixStream) -> Result<(), Error> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response: serde_json::Value = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::Flush) => serde_json::json!({ "ok": true }),
+            Ok(Request::PollMemories { project_root, since_seq, timeout_ms }) => {
+                match poll_memories(&project_root, since_seq, Duration::from_millis(timeout_ms)).await {
+                    Ok(result) => serde_json::to_value(&result)
+                        .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() })),
+                    Err(e) => serde_json::json!({ "error": e.to_string() }),
+                }
+            }
+            Err(e) => serde_json::json!({ "error": format!("invalid request: {e}") }),
+        };
+
+        let mut payload = serde_json::to_vec(&response)?;
+        payload.push(b'\n');
+        writer.write_all(&payload).await?;
+    }
+
+    Ok(())
+}
+
+/// Long-poll for memory writes. Returns immediately with every memory
+/// whose `seq > since_seq` if any already exist; otherwise re-checks every
+/// `POLL_INTERVAL` until `timeout` elapses, then returns an empty set
+/// alongside the current max `seq`. Replaces a full `get_memories` refresh
+/// with an incremental "what changed since I last looked" view.
+pub async fn poll_memories(
+    project_root: &Path,
+    since_seq: i64,
+    timeout: Duration,
+) -> Result<PollResult, Error> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let memories = storage::get_memories_since(project_root, since_seq)?;
+        if !memories.is_empty() {
+            let seq = memories.last().map(|m| m.seq).unwrap_or(since_seq);
+            return Ok(PollResult { memories, seq });
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            let seq = storage::max_seq(project_root)?;
+            return Ok(PollResult { memories: Vec::new(), seq });
+        }
+
+        tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
@@ -5,7 +5,11 @@
 
 pub mod cli;
 pub mod config;
+pub mod embedder;
 pub mod error;
+pub mod extensions;
+pub mod fsignore;
+pub mod ipc;
 pub mod mcp;
 pub mod storage;
 
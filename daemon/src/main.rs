@@ -7,7 +7,11 @@ use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 mod cli;
 mod config;
+mod embedder;
 mod error;
+mod extensions;
+mod fsignore;
+mod ipc;
 mod mcp;
 mod storage;
 
@@ -35,11 +39,19 @@ enum Commands {
     },
 
     /// Show Squirrel status
-    Status,
+    Status {
+        /// Restrict the registered-projects list to those carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
 
     /// Start MCP server (called by AI tool config, not user)
     #[command(name = "mcp-serve")]
-    McpServe,
+    McpServe {
+        /// Serve over HTTP/SSE on this port instead of stdio.
+        #[arg(long)]
+        http: Option<u16>,
+    },
 
     /// Internal commands (used by git hooks)
     #[command(hide = true, name = "_internal")]
@@ -81,13 +93,19 @@ fn main() -> Result<(), Error> {
         Some(Commands::Goaway { force }) => {
             cli::goaway::run(force)?;
         }
-        Some(Commands::Status) => {
-            let exit_code = cli::status::run()?;
+        Some(Commands::Status { tag }) => {
+            let exit_code = cli::status::run(tag.as_deref())?;
             if exit_code != 0 {
                 std::process::exit(exit_code);
             }
         }
-        Some(Commands::McpServe) => {
+        Some(Commands::McpServe { http: Some(port) }) => {
+            let project_root = std::env::current_dir()?;
+            tokio::runtime::Runtime::new()
+                .map_err(Error::Io)?
+                .block_on(mcp::http::run(project_root, port))?;
+        }
+        Some(Commands::McpServe { http: None }) => {
             mcp::run()?;
         }
         Some(Commands::Internal { cmd }) => match cmd {
@@ -0,0 +1,124 @@
+//! Streamable HTTP/SSE transport for the MCP server (`sqrl mcp-serve --http <port>`).
+//!
+//! Reuses the same [`handle_request`] dispatcher as the stdio transport: a
+//! client POSTs one or more JSON-RPC requests to `/mcp` and gets back either
+//! a single `application/json` response, or — if it sends `Accept:
+//! text/event-stream` — a `text/event-stream` with each response framed as
+//! an SSE `data:` event. A session id minted on `initialize` is returned via
+//! the `Mcp-Session-Id` header and should be echoed back on subsequent
+//! requests so several clients can share one server process.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::sse::{Event, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures_util::stream;
+use serde_json::Value;
+use tracing::info;
+
+use crate::error::Error;
+use crate::extensions::ExtensionRegistry;
+
+use super::policy::PolicyStore;
+use super::{handle_request, JsonRpcRequest, JsonRpcResponse};
+
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Shared server state across every connection.
+struct ServerState {
+    policy_store: PolicyStore,
+    registry: ExtensionRegistry,
+}
+
+/// Run the MCP server over HTTP/SSE on `port`, instead of stdio.
+pub async fn run(project_root: PathBuf, port: u16) -> Result<(), Error> {
+    let policy_store = PolicyStore::watch(project_root)?;
+    let registry = ExtensionRegistry::built_in();
+    let state = Arc::new(ServerState {
+        policy_store,
+        registry,
+    });
+
+    let app = Router::new().route("/mcp", post(handle_post)).with_state(state);
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    info!("MCP HTTP/SSE server listening on http://{}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await.map_err(Error::Io)?;
+    axum::serve(listener, app).await.map_err(Error::Io)?;
+
+    Ok(())
+}
+
+/// Dispatch one or more JSON-RPC requests (a bare object or a batch array)
+/// through the shared [`handle_request`], then reply as a single JSON body
+/// or, if the client asked for it, as an SSE stream of response frames.
+async fn handle_post(State(state): State<Arc<ServerState>>, headers: HeaderMap, Json(body): Json<Value>) -> Response {
+    let requests: Vec<JsonRpcRequest> = match body {
+        Value::Array(items) => items
+            .into_iter()
+            .filter_map(|v| serde_json::from_value(v).ok())
+            .collect(),
+        single => match serde_json::from_value(single) {
+            Ok(req) => vec![req],
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid JSON-RPC request: {e}"),
+                )
+                    .into_response()
+            }
+        },
+    };
+
+    let mut session_id = headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let mut responses: Vec<JsonRpcResponse> = Vec::new();
+    for request in &requests {
+        if request.id.is_none() && request.method.starts_with("notifications/") {
+            continue;
+        }
+        if request.method == "initialize" && session_id.is_none() {
+            session_id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        responses.push(handle_request(request, &state.policy_store, &state.registry));
+    }
+
+    let mut response_headers = HeaderMap::new();
+    if let Some(id) = session_id {
+        if let Ok(value) = HeaderValue::from_str(&id) {
+            response_headers.insert(SESSION_HEADER, value);
+        }
+    }
+
+    let wants_sse = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("text/event-stream"))
+        .unwrap_or(false);
+
+    if wants_sse {
+        let events = responses
+            .into_iter()
+            .map(|r| {
+                let data = serde_json::to_string(&r).unwrap_or_default();
+                Ok::<_, Infallible>(Event::default().data(data))
+            })
+            .collect::<Vec<_>>();
+        (response_headers, Sse::new(stream::iter(events))).into_response()
+    } else if responses.len() == 1 {
+        (response_headers, Json(responses.into_iter().next().unwrap())).into_response()
+    } else {
+        (response_headers, Json(responses)).into_response()
+    }
+}
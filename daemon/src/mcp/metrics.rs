@@ -0,0 +1,28 @@
+//! Process-wide counters for MCP `tools/call` invocations, scraped by the
+//! dashboard's `/metrics` endpoint (see `dashboard::metrics`).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Counts keyed by (tool name, succeeded).
+fn counters() -> &'static Mutex<HashMap<(String, bool), u64>> {
+    static COUNTERS: OnceLock<Mutex<HashMap<(String, bool), u64>>> = OnceLock::new();
+    COUNTERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record one `tools/call` invocation of `tool`, broken down by whether it
+/// succeeded. Called from `handle_request`'s `tools/call` arm.
+pub fn record_tool_call(tool: &str, success: bool) {
+    let mut counts = counters().lock().unwrap_or_else(|e| e.into_inner());
+    *counts.entry((tool.to_string(), success)).or_insert(0) += 1;
+}
+
+/// Snapshot of `(tool, succeeded, count)` triples, for rendering into
+/// Prometheus text format.
+pub fn snapshot() -> Vec<(String, bool, u64)> {
+    let counts = counters().lock().unwrap_or_else(|e| e.into_inner());
+    counts
+        .iter()
+        .map(|((tool, success), count)| (tool.clone(), *success, *count))
+        .collect()
+}
@@ -11,8 +11,14 @@ use serde_json::{json, Value};
 use tracing::{debug, error, info};
 
 use crate::error::Error;
+use crate::extensions::ExtensionRegistry;
 use crate::storage;
 
+pub mod http;
+pub mod metrics;
+pub mod policy;
+use policy::PolicyStore;
+
 const PROTOCOL_VERSION: &str = "2024-11-05";
 const SERVER_NAME: &str = "squirrel";
 const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -63,60 +69,91 @@ impl JsonRpcResponse {
     }
 }
 
-/// MCP tool definitions.
-fn get_tools() -> Value {
-    json!({
-        "tools": [
-            {
-                "name": "squirrel_store_memory",
-                "description": "Store a behavioral correction. Use when the user corrects you or you learn a project rule.",
-                "inputSchema": {
-                    "type": "object",
-                    "properties": {
-                        "content": {
-                            "type": "string",
-                            "description": "An actionable instruction: 'Do X', 'Don't do Y', or 'When Z, do W' (1-2 sentences)"
-                        },
-                        "memory_type": {
-                            "type": "string",
-                            "enum": ["preference", "project"],
-                            "description": "Type: preference (global user preference), project (project-specific rule)"
-                        },
-                        "tags": {
-                            "type": "array",
-                            "items": { "type": "string" },
-                            "description": "Tags for organization"
-                        }
+/// MCP tool definitions, including any registered extension tools.
+fn get_tools(registry: &ExtensionRegistry) -> Value {
+    let mut tools = vec![
+        json!({
+            "name": "squirrel_store_memory",
+            "description": "Store a behavioral correction. Use when the user corrects you or you learn a project rule.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "An actionable instruction: 'Do X', 'Don't do Y', or 'When Z, do W' (1-2 sentences)"
                     },
-                    "required": ["content", "memory_type"]
-                }
-            },
-            {
-                "name": "squirrel_get_memory",
-                "description": "Get behavioral corrections from Squirrel. Call at session start or before making choices.",
-                "inputSchema": {
-                    "type": "object",
-                    "properties": {
-                        "memory_type": {
-                            "type": "string",
-                            "enum": ["preference", "project"],
-                            "description": "Filter by type. Omit to get all."
-                        },
-                        "tags": {
-                            "type": "array",
-                            "items": { "type": "string" },
-                            "description": "Filter by tags. Omit to get all."
-                        },
-                        "limit": {
-                            "type": "integer",
-                            "description": "Max memories to return. Default 50."
-                        }
+                    "memory_type": {
+                        "type": "string",
+                        "enum": ["preference", "project"],
+                        "description": "Type: preference (global user preference), project (project-specific rule)"
                     },
-                    "required": []
-                }
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Tags for organization"
+                    }
+                },
+                "required": ["content", "memory_type"]
+            }
+        }),
+        json!({
+            "name": "squirrel_get_memory",
+            "description": "Get behavioral corrections from Squirrel. Call at session start or before making choices.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "memory_type": {
+                        "type": "string",
+                        "enum": ["preference", "project"],
+                        "description": "Filter by type. Omit to get all."
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Filter by tags. Omit to get all."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Max memories to return. Default 50."
+                    }
+                },
+                "required": []
+            }
+        }),
+        json!({
+            "name": "squirrel_search_memory",
+            "description": "Full-text ranked search over stored memories. Use to find corrections by topic instead of browsing by type/tags.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Free-text query. Terms are ANDed; best matches (BM25-ranked) come first."
+                    },
+                    "memory_type": {
+                        "type": "string",
+                        "enum": ["preference", "project"],
+                        "description": "Filter by type. Omit to search all."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Max memories to return. Default 50."
+                    }
+                },
+                "required": ["query"]
             }
-        ]
-    })
+        }),
+    ];
+
+    for ext in registry.mcp_tools() {
+        tools.push(json!({
+            "name": ext.name(),
+            "description": ext.description(),
+            "inputSchema": ext.schema(),
+        }));
+    }
+
+    json!({ "tools": tools })
 }
 
 /// Get project root from MCP params, falling back to cwd.
@@ -205,8 +242,50 @@ fn handle_get_memory(params: &Value) -> Result<Value, Error> {
     }))
 }
 
-/// Handle incoming MCP request.
-fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
+/// Handle squirrel_search_memory.
+fn handle_search_memory(params: &Value) -> Result<Value, Error> {
+    let args = params.get("arguments").unwrap_or(params);
+
+    let query = args
+        .get("query")
+        .and_then(|q| q.as_str())
+        .ok_or_else(|| Error::Mcp("Missing 'query' parameter".to_string()))?;
+
+    let memory_type = args.get("memory_type").and_then(|t| t.as_str());
+    let limit = args.get("limit").and_then(|l| l.as_i64());
+
+    let project_root = get_project_root(params)?;
+    let memories = storage::search_memories(&project_root, query, memory_type, limit)?;
+
+    if memories.is_empty() {
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": "No matching memories found."
+            }]
+        }));
+    }
+
+    let mut text = String::new();
+    for m in &memories {
+        text.push_str(&format!("- [{}, used {}x] {}\n", m.memory_type, m.use_count, m.content));
+    }
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": text.trim_end()
+        }]
+    }))
+}
+
+/// Handle incoming MCP request, enforcing `policy` on `tools/call` and
+/// dispatching to registered extension tools when a name isn't built in.
+fn handle_request(
+    request: &JsonRpcRequest,
+    policy_store: &PolicyStore,
+    registry: &ExtensionRegistry,
+) -> JsonRpcResponse {
     let id = request.id.clone().unwrap_or(Value::Null);
 
     match request.method.as_str() {
@@ -234,7 +313,7 @@ fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
 
         "tools/list" => {
             debug!("MCP tools/list");
-            JsonRpcResponse::success(id, get_tools())
+            JsonRpcResponse::success(id, get_tools(registry))
         }
 
         "tools/call" => {
@@ -246,16 +325,39 @@ fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
 
             debug!(tool = tool_name, "MCP tools/call");
 
-            match tool_name {
-                "squirrel_store_memory" => match handle_store_memory(&request.params) {
-                    Ok(result) => JsonRpcResponse::success(id, result),
-                    Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
-                },
-                "squirrel_get_memory" => match handle_get_memory(&request.params) {
-                    Ok(result) => JsonRpcResponse::success(id, result),
-                    Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
+            if !policy_store.current().allows(tool_name) {
+                return JsonRpcResponse::error(
+                    id,
+                    -32000,
+                    format!("Tool '{}' is denied by policy", tool_name),
+                );
+            }
+
+            let outcome = match tool_name {
+                "squirrel_store_memory" => handle_store_memory(&request.params),
+                "squirrel_get_memory" => handle_get_memory(&request.params),
+                "squirrel_search_memory" => handle_search_memory(&request.params),
+                _ => match registry.find_mcp_tool(tool_name) {
+                    Some(ext) => {
+                        let arguments = request.params.get("arguments").unwrap_or(&Value::Null);
+                        ext.handle(arguments)
+                    }
+                    None => {
+                        metrics::record_tool_call(tool_name, false);
+                        return JsonRpcResponse::error(
+                            id,
+                            -32601,
+                            format!("Unknown tool: {}", tool_name),
+                        );
+                    }
                 },
-                _ => JsonRpcResponse::error(id, -32601, format!("Unknown tool: {}", tool_name)),
+            };
+
+            metrics::record_tool_call(tool_name, outcome.is_ok());
+
+            match outcome {
+                Ok(result) => JsonRpcResponse::success(id, result),
+                Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
             }
         }
 
@@ -270,6 +372,10 @@ fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
 pub fn run() -> Result<(), Error> {
     info!("Starting MCP server");
 
+    let project_root = std::env::current_dir()?;
+    let policy_store = PolicyStore::watch(project_root)?;
+    let registry = ExtensionRegistry::built_in();
+
     let stdin = std::io::stdin();
     let mut stdout = std::io::stdout();
 
@@ -281,8 +387,8 @@ pub fn run() -> Result<(), Error> {
 
         debug!(request = %line, "MCP request");
 
-        let request: JsonRpcRequest = match serde_json::from_str(&line) {
-            Ok(req) => req,
+        let value: Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
             Err(e) => {
                 error!(error = %e, "Failed to parse MCP request");
                 let response =
@@ -294,21 +400,75 @@ pub fn run() -> Result<(), Error> {
             }
         };
 
-        // Skip notifications (no id)
-        if request.id.is_none() && request.method.starts_with("notifications/") {
-            debug!(method = request.method, "Skipping notification");
+        // Per JSON-RPC 2.0, a line may hold a single request object or a
+        // batch array of them; an empty batch is itself an error.
+        let responses = match value {
+            Value::Array(items) => {
+                if items.is_empty() {
+                    let response = JsonRpcResponse::error(
+                        Value::Null,
+                        -32600,
+                        "Invalid Request: empty batch".to_string(),
+                    );
+                    writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
+                    stdout.flush()?;
+                    continue;
+                }
+                items
+                    .into_iter()
+                    .filter_map(|item| dispatch_request_value(item, &policy_store, &registry))
+                    .collect::<Vec<_>>()
+            }
+            single => dispatch_request_value(single, &policy_store, &registry)
+                .into_iter()
+                .collect(),
+        };
+
+        if responses.is_empty() {
+            // Every element was a notification; JSON-RPC says send nothing back.
             continue;
         }
 
-        let response = handle_request(&request);
-        let response_str = serde_json::to_string(&response)?;
+        let out = if responses.len() == 1 {
+            serde_json::to_string(&responses[0])?
+        } else {
+            serde_json::to_string(&responses)?
+        };
 
-        debug!(response = %response_str, "MCP response");
+        debug!(response = %out, "MCP response");
 
-        writeln!(stdout, "{}", response_str)?;
+        writeln!(stdout, "{}", out)?;
         stdout.flush()?;
     }
 
     info!("MCP server stopped");
     Ok(())
 }
+
+/// Parse one JSON-RPC request value and dispatch it through
+/// [`handle_request`]. An element that isn't a valid request object gets
+/// an `Invalid Request` error response with a `null` id; `None` is
+/// returned only for notifications, which get no response at all.
+fn dispatch_request_value(
+    value: Value,
+    policy_store: &PolicyStore,
+    registry: &ExtensionRegistry,
+) -> Option<JsonRpcResponse> {
+    let request: JsonRpcRequest = match serde_json::from_value(value) {
+        Ok(req) => req,
+        Err(e) => {
+            return Some(JsonRpcResponse::error(
+                Value::Null,
+                -32600,
+                format!("Invalid Request: {e}"),
+            ))
+        }
+    };
+
+    if request.id.is_none() && request.method.starts_with("notifications/") {
+        debug!(method = request.method, "Skipping notification");
+        return None;
+    }
+
+    Some(handle_request(&request, policy_store, registry))
+}
@@ -0,0 +1,174 @@
+//! Hot-reloadable MCP tool policy.
+//!
+//! Loads the project policy (`.sqrl/policy.toml`) and the global policy
+//! (`~/.sqrl/policy.toml`), with the project overriding the global, and
+//! watches both files so a running `mcp-serve` process picks up edits
+//! without needing to be restarted.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+
+use crate::error::Error;
+
+/// Tool access policy for the MCP server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Policy {
+    /// Tool names explicitly allowed. Empty means "all tools allowed".
+    #[serde(default)]
+    pub allow_tools: Vec<String>,
+
+    /// Tool names explicitly denied, checked before `allow_tools`, so a
+    /// tool on both lists is denied.
+    #[serde(default)]
+    pub deny_tools: Vec<String>,
+}
+
+impl Policy {
+    /// Whether `tool` may be invoked under this policy.
+    pub fn allows(&self, tool: &str) -> bool {
+        if self.deny_tools.iter().any(|t| t == tool) {
+            return false;
+        }
+        self.allow_tools.is_empty() || self.allow_tools.iter().any(|t| t == tool)
+    }
+}
+
+/// Project policy file path.
+pub fn project_policy_path(project_root: &Path) -> PathBuf {
+    project_root.join(".sqrl").join("policy.toml")
+}
+
+/// Global policy file path (`~/.sqrl/policy.toml`).
+pub fn global_policy_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".sqrl").join("policy.toml"))
+}
+
+/// Load and merge project + global policy, project overriding global.
+/// A missing file is treated as an empty policy; a present-but-invalid file
+/// is an error so the caller can decide whether to keep the previous policy.
+fn load_merged(project_root: &Path) -> Result<Policy, Error> {
+    let mut merged = load_one(&global_policy_path().unwrap_or_default())?.unwrap_or_default();
+
+    if let Some(project) = load_one(&project_policy_path(project_root))? {
+        if !project.allow_tools.is_empty() {
+            merged.allow_tools = project.allow_tools;
+        }
+        merged.deny_tools.extend(project.deny_tools);
+    }
+
+    Ok(merged)
+}
+
+fn load_one(path: &Path) -> Result<Option<Policy>, Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let policy: Policy =
+        toml::from_str(&content).map_err(|e| Error::ConfigParse(format!("{}: {e}", path.display())))?;
+    Ok(Some(policy))
+}
+
+/// A policy that can be hot-reloaded while the MCP server is running.
+pub struct PolicyStore {
+    current: Arc<RwLock<Policy>>,
+    project_root: PathBuf,
+    // Kept alive for the lifetime of the store; dropping it stops watching.
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl PolicyStore {
+    /// Load the initial merged policy and start watching both policy files
+    /// for changes, swapping the in-memory policy atomically on each valid
+    /// edit. Invalid TOML on reload is rejected and the previous policy is
+    /// left intact.
+    pub fn watch(project_root: PathBuf) -> Result<Self, Error> {
+        let initial = load_merged(&project_root).unwrap_or_else(|e| {
+            warn!(error = %e, "Invalid policy at startup, starting with an empty policy");
+            Policy::default()
+        });
+        let current = Arc::new(RwLock::new(initial));
+
+        let watch_root = project_root.clone();
+        let watched = current.clone();
+        let watcher_result = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_err() {
+                return;
+            }
+            match load_merged(&watch_root) {
+                Ok(policy) => {
+                    *watched.write().unwrap() = policy;
+                    info!("Policy reloaded");
+                }
+                Err(e) => {
+                    error!(error = %e, "Invalid policy on reload, keeping previous policy");
+                }
+            }
+        });
+
+        let watcher = match watcher_result {
+            Ok(mut watcher) => {
+                for path in [
+                    Some(project_policy_path(&project_root)),
+                    global_policy_path(),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    if let Some(parent) = path.parent() {
+                        if parent.exists() {
+                            let _ = watcher.watch(parent, RecursiveMode::NonRecursive);
+                        }
+                    }
+                }
+                Some(watcher)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to start policy file watcher, reload disabled");
+                None
+            }
+        };
+
+        Ok(Self {
+            current,
+            project_root,
+            _watcher: watcher,
+        })
+    }
+
+    /// Current merged policy.
+    pub fn current(&self) -> Policy {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Force a re-read of both policy files without waiting for the
+    /// filesystem watcher. Nothing calls it: `sqrl policy reload` rewrites
+    /// the files with [`touch`] and relies on the watcher instead.
+    #[allow(dead_code)]
+    pub fn force_reload(&self) {
+        match load_merged(&self.project_root) {
+            Ok(policy) => {
+                *self.current.write().unwrap() = policy;
+                debug!("Policy force-reloaded");
+            }
+            Err(e) => {
+                error!(error = %e, "Invalid policy on forced reload, keeping previous policy");
+            }
+        }
+    }
+}
+
+/// Touch a policy file's mtime so a running server's watcher picks it up,
+/// without changing its content. Used by `sqrl policy reload`.
+pub fn touch(path: &Path) -> Result<(), Error> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(path)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
@@ -3,14 +3,19 @@
 //! SCHEMA-001: memories in <repo>/.sqrl/memory.db
 //! SCHEMA-002: doc_debt in <repo>/.sqrl/memory.db
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use rusqlite::{Connection, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
 
+use crate::config::{Config, DocsConfig, StorageConfig, SubprojectConfig};
 use crate::error::Error;
 
+pub mod vault;
+use vault::Vault;
+
 // === Database Path ===
 
 /// Get the project database path.
@@ -18,6 +23,136 @@ fn db_path(project_root: &Path) -> PathBuf {
     project_root.join(".sqrl").join("memory.db")
 }
 
+/// Open `path` with the tuning every connection in this module needs:
+/// WAL journal mode so the `LogWatcher` daemon can keep reading while a
+/// CLI command holds the write lock, `synchronous=NORMAL` (the
+/// recommended pairing with WAL), foreign key enforcement, and a
+/// busy_timeout so transient lock contention retries instead of
+/// immediately failing with `SQLITE_BUSY`. The timeout comes from the
+/// project's `Config` when one is loadable, falling back to
+/// `StorageConfig::default()` otherwise (some callers run before
+/// `.sqrl/config.yaml` exists). Also brings the schema up to date via
+/// [`run_migrations`], so every caller gets a connection that's both
+/// tuned and ready to query without a separate `ensure_*` call.
+fn open_tuned(path: &Path) -> Result<Connection, Error> {
+    let mut conn = Connection::open(path)?;
+
+    let busy_timeout_ms = path
+        .parent() // .sqrl
+        .and_then(Path::parent) // project root
+        .and_then(|root| Config::load(root).ok())
+        .map(|config| config.storage.busy_timeout_ms)
+        .unwrap_or_else(|| StorageConfig::default().busy_timeout_ms);
+
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "busy_timeout", busy_timeout_ms as i64)?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+
+    run_migrations(&mut conn)?;
+
+    Ok(conn)
+}
+
+/// One additive schema change, applied exactly once per database and
+/// tracked with SQLite's `PRAGMA user_version` (the migration at index
+/// `i` bumps `user_version` to `i + 1` once its statements commit).
+/// Migrations are append-only: never edit one that's already shipped,
+/// add a new one instead, the same way any other migration tool works.
+struct Migration {
+    name: &'static str,
+    statements: &'static [&'static str],
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        name: "create_memories_table",
+        statements: &[
+            "CREATE TABLE IF NOT EXISTS memories (
+                id           TEXT PRIMARY KEY,
+                memory_type  TEXT NOT NULL,
+                content      TEXT NOT NULL,
+                tags         TEXT DEFAULT '[]',
+                use_count    INTEGER DEFAULT 1,
+                created_at   TEXT NOT NULL,
+                updated_at   TEXT NOT NULL,
+                sensitive    INTEGER DEFAULT 0
+            )",
+            "CREATE INDEX IF NOT EXISTS idx_memories_type ON memories(memory_type)",
+            "CREATE INDEX IF NOT EXISTS idx_memories_use_count ON memories(use_count DESC)",
+        ],
+    },
+    Migration {
+        name: "create_memories_fts",
+        statements: &[
+            "CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts
+             USING fts5(content, content='memories', content_rowid='rowid')",
+            "CREATE TRIGGER IF NOT EXISTS memories_fts_ai AFTER INSERT ON memories BEGIN
+                INSERT INTO memories_fts(rowid, content) VALUES (new.rowid, new.content);
+             END",
+            "CREATE TRIGGER IF NOT EXISTS memories_fts_ad AFTER DELETE ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content) VALUES('delete', old.rowid, old.content);
+             END",
+            "CREATE TRIGGER IF NOT EXISTS memories_fts_au AFTER UPDATE ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content) VALUES('delete', old.rowid, old.content);
+                INSERT INTO memories_fts(rowid, content) VALUES (new.rowid, new.content);
+             END",
+            "INSERT INTO memories_fts(rowid, content)
+             SELECT rowid, content FROM memories
+             WHERE rowid NOT IN (SELECT rowid FROM memories_fts)",
+        ],
+    },
+    Migration {
+        name: "create_doc_debt_table",
+        statements: &[
+            "CREATE TABLE IF NOT EXISTS doc_debt (
+                id              TEXT PRIMARY KEY,
+                commit_sha      TEXT NOT NULL,
+                commit_message  TEXT,
+                code_files      TEXT NOT NULL,
+                expected_docs   TEXT NOT NULL,
+                detection_rule  TEXT NOT NULL,
+                resolved        INTEGER DEFAULT 0,
+                resolved_at     TEXT,
+                created_at      TEXT NOT NULL
+            )",
+            "CREATE INDEX IF NOT EXISTS idx_doc_debt_commit ON doc_debt(commit_sha)",
+            "CREATE INDEX IF NOT EXISTS idx_doc_debt_resolved ON doc_debt(resolved)",
+        ],
+    },
+    Migration {
+        name: "add_memories_seq",
+        statements: &[
+            "ALTER TABLE memories ADD COLUMN seq INTEGER",
+            "CREATE INDEX IF NOT EXISTS idx_memories_seq ON memories(seq)",
+            // Existing rows predate this column; rowid is already
+            // monotonically increasing in insertion order, so it's a
+            // faithful backfill.
+            "UPDATE memories SET seq = rowid WHERE seq IS NULL",
+        ],
+    },
+];
+
+/// Bring `conn`'s schema up to date: read `user_version`, then run every
+/// migration from there onward inside its own transaction, bumping
+/// `user_version` to `index + 1` right before that transaction commits.
+fn run_migrations(conn: &mut Connection) -> SqliteResult<()> {
+    let current_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let current_version = current_version.max(0) as usize;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+        let tx = conn.transaction()?;
+        for statement in migration.statements {
+            tx.execute(statement, [])?;
+        }
+        tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+        tx.commit()?;
+        tracing::debug!("Applied migration {}: {}", index, migration.name);
+    }
+
+    Ok(())
+}
+
 // === Memory (SCHEMA-001) ===
 
 /// A stored memory.
@@ -30,31 +165,16 @@ pub struct Memory {
     pub use_count: i64,
     pub created_at: String,
     pub updated_at: String,
-}
-
-/// Ensure the memories table exists.
-fn ensure_memories_table(conn: &Connection) -> SqliteResult<()> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS memories (
-            id           TEXT PRIMARY KEY,
-            memory_type  TEXT NOT NULL,
-            content      TEXT NOT NULL,
-            tags         TEXT DEFAULT '[]',
-            use_count    INTEGER DEFAULT 1,
-            created_at   TEXT NOT NULL,
-            updated_at   TEXT NOT NULL
-        )",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_memories_type ON memories(memory_type)",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_memories_use_count ON memories(use_count DESC)",
-        [],
-    )?;
-    Ok(())
+    /// Whether `content` is sealed in a [`vault::Vault`] rather than stored
+    /// as plaintext. Set by [`store_sensitive_memory`]; never by
+    /// [`store_memory`].
+    #[serde(default)]
+    pub sensitive: bool,
+    /// Monotonically increasing write sequence, bumped on every insert and
+    /// dedup-update. Lets [`get_memories_since`] hand a client an
+    /// incremental view instead of re-fetching the whole table.
+    #[serde(default)]
+    pub seq: i64,
 }
 
 /// Store a memory. Deduplicates by content (increments use_count if exists).
@@ -69,8 +189,7 @@ pub fn store_memory(
         fs::create_dir_all(parent)?;
     }
 
-    let conn = Connection::open(&path)?;
-    ensure_memories_table(&conn)?;
+    let conn = open_tuned(&path)?;
 
     // Check for existing memory with same content
     let existing: Option<(String, i64)> = conn
@@ -85,7 +204,8 @@ pub fn store_memory(
         let new_count = use_count + 1;
         let now = chrono::Utc::now().to_rfc3339();
         conn.execute(
-            "UPDATE memories SET use_count = ?1, updated_at = ?2 WHERE id = ?3",
+            "UPDATE memories SET use_count = ?1, updated_at = ?2, \
+             seq = (SELECT COALESCE(MAX(seq), 0) + 1 FROM memories) WHERE id = ?3",
             rusqlite::params![new_count, now, id],
         )?;
         Ok((id, true, new_count))
@@ -95,14 +215,152 @@ pub fn store_memory(
         let tags_json = serde_json::to_string(tags)?;
 
         conn.execute(
-            "INSERT INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6)",
+            "INSERT INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at, seq)
+             VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6, (SELECT COALESCE(MAX(seq), 0) + 1 FROM memories))",
             rusqlite::params![id, memory_type, content, tags_json, now, now],
         )?;
         Ok((id, false, 1))
     }
 }
 
+/// Store a memory with `content` sealed in `vault`, authenticated with the
+/// memory's own id as associated data. Unlike [`store_memory`], this never
+/// deduplicates by content — the ciphertext differs on every call even for
+/// identical plaintext, so there's nothing to compare against.
+pub fn store_sensitive_memory(
+    project_root: &Path,
+    vault: &Vault,
+    memory_type: &str,
+    content: &str,
+    tags: &[String],
+) -> Result<String, Error> {
+    let path = db_path(project_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let conn = open_tuned(&path)?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let tags_json = serde_json::to_string(tags)?;
+    let sealed = vault.seal(content.as_bytes(), id.as_bytes())?;
+    let stored = serde_json::to_string(&sealed)?;
+
+    conn.execute(
+        "INSERT INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at, sensitive, seq)
+         VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6, 1, (SELECT COALESCE(MAX(seq), 0) + 1 FROM memories))",
+        rusqlite::params![id, memory_type, stored, tags_json, now, now],
+    )?;
+    Ok(id)
+}
+
+/// Unseal a sensitive memory's content. Fails if `id` doesn't name a
+/// sensitive memory, or if `vault` can't open it (wrong passphrase, or the
+/// record was tampered with).
+pub fn get_sensitive_memory_content(
+    project_root: &Path,
+    vault: &Vault,
+    id: &str,
+) -> Result<String, Error> {
+    let path = db_path(project_root);
+    let conn = open_tuned(&path)?;
+
+    let (content, sensitive): (String, bool) = conn.query_row(
+        "SELECT content, sensitive FROM memories WHERE id = ?1",
+        [id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if !sensitive {
+        return Err(Error::ConfigParse(format!("memory {id} is not sensitive")));
+    }
+
+    let sealed: vault::SealedSecret = serde_json::from_str(&content)?;
+    let plaintext = vault.open(&sealed, id.as_bytes())?;
+    String::from_utf8(plaintext)
+        .map_err(|e| Error::ConfigParse(format!("decrypted memory {id} was not valid UTF-8: {e}")))
+}
+
+
+/// Quote each whitespace-separated term of a free-text query so stray FTS5
+/// operators (`-`, `*`, `:`, unbalanced quotes, ...) in user input can't be
+/// interpreted as query syntax. Terms are implicitly ANDed by FTS5.
+fn sanitize_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Full-text search over memory content, ranked by BM25 (FTS5's built-in
+/// ranking: term frequency within a row, normalized by row length, weighed
+/// against corpus-wide inverse document frequency) so short highly-relevant
+/// corrections outrank long tangential ones. Falls back to the plain
+/// type/tag filter path (`get_memories`) when `query` is blank.
+pub fn search_memories(
+    project_root: &Path,
+    query: &str,
+    memory_type: Option<&str>,
+    limit: Option<i64>,
+) -> Result<Vec<Memory>, Error> {
+    if query.trim().is_empty() {
+        return get_memories(project_root, memory_type, None, limit);
+    }
+
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let conn = open_tuned(&path)?;
+
+    let fts_query = sanitize_fts_query(query);
+    let lim = limit.unwrap_or(50);
+
+    let mut sql = String::from(
+        "SELECT m.id, m.memory_type, m.content, m.tags, m.use_count, m.created_at, m.updated_at, m.seq
+         FROM memories_fts f JOIN memories m ON m.rowid = f.rowid
+         WHERE memories_fts MATCH ?1 AND m.sensitive = 0",
+    );
+    if memory_type.is_some() {
+        sql.push_str(" AND m.memory_type = ?3");
+    }
+    sql.push_str(" ORDER BY bm25(memories_fts) LIMIT ?2");
+
+    let mut stmt = conn.prepare(&sql)?;
+
+    let row_mapper = |row: &rusqlite::Row| {
+        let tags_json: String = row.get(3)?;
+        Ok(Memory {
+            id: row.get(0)?,
+            memory_type: row.get(1)?,
+            content: row.get(2)?,
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            use_count: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+            sensitive: false,
+            seq: row.get(7)?,
+        })
+    };
+
+    let mut memories = Vec::new();
+    if let Some(mt) = memory_type {
+        let rows = stmt.query_map(rusqlite::params![fts_query, lim, mt], row_mapper)?;
+        for row in rows {
+            memories.push(row?);
+        }
+    } else {
+        let rows = stmt.query_map(rusqlite::params![fts_query, lim], row_mapper)?;
+        for row in rows {
+            memories.push(row?);
+        }
+    }
+
+    Ok(memories)
+}
+
 /// Get memories, optionally filtered by type and/or tags.
 pub fn get_memories(
     project_root: &Path,
@@ -115,7 +373,7 @@ pub fn get_memories(
         return Ok(vec![]);
     }
 
-    let conn = Connection::open(&path)?;
+    let conn = open_tuned(&path)?;
 
     // Check if table exists
     let table_exists: i32 = conn.query_row(
@@ -128,7 +386,7 @@ pub fn get_memories(
     }
 
     let mut sql = String::from(
-        "SELECT id, memory_type, content, tags, use_count, created_at, updated_at FROM memories",
+        "SELECT id, memory_type, content, tags, use_count, created_at, updated_at, sensitive, seq FROM memories",
     );
     let mut conditions = Vec::new();
 
@@ -151,14 +409,24 @@ pub fn get_memories(
 
     let row_mapper = |row: &rusqlite::Row| {
         let tags_json: String = row.get(3)?;
+        let sensitive: bool = row.get(7)?;
         Ok(Memory {
             id: row.get(0)?,
             memory_type: row.get(1)?,
-            content: row.get(2)?,
+            // Sealed content is meaningless base64 anyway, but callers that
+            // forget to check `sensitive` get a clear placeholder instead
+            // of raw ciphertext.
+            content: if sensitive {
+                "[sensitive memory: use get_sensitive_memory_content to unseal]".to_string()
+            } else {
+                row.get(2)?
+            },
             tags: serde_json::from_str(&tags_json).unwrap_or_default(),
             use_count: row.get(4)?,
             created_at: row.get(5)?,
             updated_at: row.get(6)?,
+            sensitive,
+            seq: row.get(8)?,
         })
     };
 
@@ -185,6 +453,63 @@ pub fn get_memories(
     Ok(memories)
 }
 
+/// Every memory written since `since_seq` (exclusive), ordered by `seq` so
+/// a caller can fold them into an incremental view and remember the last
+/// row's `seq` as its new watermark. Used by `ipc::poll_memories` instead
+/// of a full [`get_memories`] refresh.
+pub fn get_memories_since(project_root: &Path, since_seq: i64) -> Result<Vec<Memory>, Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let conn = open_tuned(&path)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, memory_type, content, tags, use_count, created_at, updated_at, sensitive, seq
+         FROM memories WHERE seq > ?1 ORDER BY seq ASC",
+    )?;
+    let rows = stmt.query_map([since_seq], |row| {
+        let tags_json: String = row.get(3)?;
+        let sensitive: bool = row.get(7)?;
+        Ok(Memory {
+            id: row.get(0)?,
+            memory_type: row.get(1)?,
+            content: if sensitive {
+                "[sensitive memory: use get_sensitive_memory_content to unseal]".to_string()
+            } else {
+                row.get(2)?
+            },
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            use_count: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+            sensitive,
+            seq: row.get(8)?,
+        })
+    })?;
+
+    let mut memories = Vec::new();
+    for row in rows {
+        memories.push(row?);
+    }
+    Ok(memories)
+}
+
+/// The highest `seq` currently stored, or `0` if the database or table
+/// doesn't exist yet. `ipc::poll_memories` returns this as the watermark
+/// for a poll that found nothing new.
+pub fn max_seq(project_root: &Path) -> Result<i64, Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let conn = open_tuned(&path)?;
+    let seq: Option<i64> = conn.query_row("SELECT MAX(seq) FROM memories", [], |row| row.get(0))?;
+    Ok(seq.unwrap_or(0))
+}
+
 /// Format memories as markdown grouped by type (for MCP response).
 pub fn format_memories_as_markdown(
     project_root: &Path,
@@ -220,6 +545,19 @@ pub fn format_memories_as_markdown(
     Ok(output.trim_end().to_string())
 }
 
+/// Delete every stored memory for a project. Used by `import --replace` to
+/// start from a clean slate before re-importing an export.
+pub fn clear_memories(project_root: &Path) -> Result<(), Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let conn = open_tuned(&path)?;
+    conn.execute("DELETE FROM memories", [])?;
+    Ok(())
+}
+
 /// Get memory count by type.
 pub fn get_memory_counts(
     project_root: &Path,
@@ -229,7 +567,7 @@ pub fn get_memory_counts(
         return Ok(std::collections::HashMap::new());
     }
 
-    let conn = Connection::open(&path)?;
+    let conn = open_tuned(&path)?;
 
     let table_exists: i32 = conn.query_row(
         "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='memories'",
@@ -273,32 +611,6 @@ pub struct DocDebt {
     pub created_at: String,
 }
 
-/// Ensure doc_debt table exists.
-fn ensure_doc_debt_table(conn: &Connection) -> SqliteResult<()> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS doc_debt (
-            id              TEXT PRIMARY KEY,
-            commit_sha      TEXT NOT NULL,
-            commit_message  TEXT,
-            code_files      TEXT NOT NULL,
-            expected_docs   TEXT NOT NULL,
-            detection_rule  TEXT NOT NULL,
-            resolved        INTEGER DEFAULT 0,
-            resolved_at     TEXT,
-            created_at      TEXT NOT NULL
-        )",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_doc_debt_commit ON doc_debt(commit_sha)",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_doc_debt_resolved ON doc_debt(resolved)",
-        [],
-    )?;
-    Ok(())
-}
 
 /// Add a doc debt entry.
 pub fn add_doc_debt(
@@ -314,8 +626,7 @@ pub fn add_doc_debt(
         fs::create_dir_all(parent)?;
     }
 
-    let conn = Connection::open(&path)?;
-    ensure_doc_debt_table(&conn)?;
+    let conn = open_tuned(&path)?;
 
     let id = uuid::Uuid::new_v4().to_string();
     let code_files_json = serde_json::to_string(code_files)?;
@@ -338,7 +649,7 @@ pub fn get_unresolved_doc_debt(project_root: &Path) -> Result<Vec<DocDebt>, Erro
         return Ok(vec![]);
     }
 
-    let conn = Connection::open(&path)?;
+    let conn = open_tuned(&path)?;
 
     let table_exists: i32 = conn.query_row(
         "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='doc_debt'",
@@ -384,7 +695,7 @@ pub fn has_doc_debt_for_commit(project_root: &Path, commit_sha: &str) -> Result<
         return Ok(false);
     }
 
-    let conn = Connection::open(&path)?;
+    let conn = open_tuned(&path)?;
 
     let table_exists: i32 = conn.query_row(
         "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='doc_debt'",
@@ -404,6 +715,391 @@ pub fn has_doc_debt_for_commit(project_root: &Path, commit_sha: &str) -> Result<
     Ok(count > 0)
 }
 
+// === Repair / Maintenance ===
+
+/// Summary of what [`repair`] did (or, under `dry_run`, would do), in the
+/// same spirit as [`get_memory_counts`]'s per-label breakdown for `status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairReport {
+    pub dry_run: bool,
+    /// `PRAGMA integrity_check` result; `true` only if it returned exactly `ok`.
+    pub integrity_ok: bool,
+    pub integrity_detail: String,
+    pub vacuumed: bool,
+    pub indexes_rebuilt: usize,
+    pub duplicates_merged: usize,
+    pub stale_doc_debt_dropped: usize,
+}
+
+/// Heal a project database: verify integrity, reclaim space, rebuild
+/// indexes, merge any `memories` rows that slipped past [`store_memory`]'s
+/// content dedup, and drop [`DocDebt`] rows whose `commit_sha` no longer
+/// exists in this repo's git history. Returns what it found/fixed without
+/// changing anything when `dry_run` is set.
+pub fn repair(project_root: &Path, dry_run: bool) -> Result<RepairReport, Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(RepairReport {
+            dry_run,
+            integrity_ok: true,
+            integrity_detail: "no database".to_string(),
+            vacuumed: false,
+            indexes_rebuilt: 0,
+            duplicates_merged: 0,
+            stale_doc_debt_dropped: 0,
+        });
+    }
+
+    let conn = open_tuned(&path)?;
+
+    let integrity_detail: String =
+        conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    let integrity_ok = integrity_detail == "ok";
+
+    let duplicates_merged = merge_duplicate_memories(&conn, dry_run)?;
+    let stale_doc_debt_dropped = drop_stale_doc_debt(&conn, project_root, dry_run)?;
+
+    let indexes_rebuilt = if dry_run {
+        REBUILDABLE_INDEXES.len()
+    } else {
+        for index in REBUILDABLE_INDEXES {
+            conn.execute_batch(&format!("DROP INDEX IF EXISTS {}", index.name))?;
+            conn.execute(index.create_sql, [])?;
+        }
+        REBUILDABLE_INDEXES.len()
+    };
+
+    let vacuumed = if dry_run {
+        false
+    } else {
+        conn.execute_batch("VACUUM")?;
+        true
+    };
+
+    Ok(RepairReport {
+        dry_run,
+        integrity_ok,
+        integrity_detail,
+        vacuumed,
+        indexes_rebuilt,
+        duplicates_merged,
+        stale_doc_debt_dropped,
+    })
+}
+
+/// One index rebuilt by [`repair`]; kept alongside the migration that first
+/// created it so the `CREATE INDEX` statement can't drift out of sync.
+struct RebuildableIndex {
+    name: &'static str,
+    create_sql: &'static str,
+}
+
+const REBUILDABLE_INDEXES: &[RebuildableIndex] = &[
+    RebuildableIndex {
+        name: "idx_memories_type",
+        create_sql: "CREATE INDEX idx_memories_type ON memories(memory_type)",
+    },
+    RebuildableIndex {
+        name: "idx_memories_use_count",
+        create_sql: "CREATE INDEX idx_memories_use_count ON memories(use_count DESC)",
+    },
+    RebuildableIndex {
+        name: "idx_doc_debt_commit",
+        create_sql: "CREATE INDEX idx_doc_debt_commit ON doc_debt(commit_sha)",
+    },
+    RebuildableIndex {
+        name: "idx_doc_debt_resolved",
+        create_sql: "CREATE INDEX idx_doc_debt_resolved ON doc_debt(resolved)",
+    },
+];
+
+/// Group `memories` by `content`, keep the oldest row (by `created_at`),
+/// sum every duplicate's `use_count` into it, and delete the rest. Guards
+/// against rows that slipped past [`store_memory`]'s dedup check, e.g. ones
+/// inserted directly or recovered from a backup.
+fn merge_duplicate_memories(conn: &Connection, dry_run: bool) -> Result<usize, Error> {
+    let mut stmt = conn.prepare(
+        "SELECT id, content, use_count, created_at FROM memories ORDER BY content, created_at ASC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    drop(stmt);
+
+    let mut groups: BTreeMap<String, Vec<(String, i64, String)>> = BTreeMap::new();
+    for (id, content, use_count, created_at) in rows {
+        groups.entry(content).or_default().push((id, use_count, created_at));
+    }
+
+    let mut merged = 0;
+    for (_, mut entries) in groups {
+        if entries.len() < 2 {
+            continue;
+        }
+        // Already sorted by created_at ASC within the group by the query.
+        let (keep_id, keep_count, _) = entries.remove(0);
+        let duplicate_count: i64 = entries.iter().map(|(_, count, _)| count).sum();
+        merged += entries.len();
+
+        if dry_run {
+            continue;
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE memories SET use_count = ?1, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![keep_count + duplicate_count, now, keep_id],
+        )?;
+        for (dup_id, _, _) in &entries {
+            conn.execute("DELETE FROM memories WHERE id = ?1", [dup_id])?;
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Drop `doc_debt` rows whose `commit_sha` no longer resolves in this
+/// repo's git history (e.g. after a rebase or history rewrite). Rows are
+/// only dropped when git positively confirms the commit is gone — if `git`
+/// can't be run at all (not installed, `project_root` isn't a repo, etc.)
+/// the row is left alone rather than treated as stale.
+fn drop_stale_doc_debt(conn: &Connection, project_root: &Path, dry_run: bool) -> Result<usize, Error> {
+    let table_exists: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='doc_debt'",
+        [],
+        |row| row.get(0),
+    )?;
+    if table_exists == 0 {
+        return Ok(0);
+    }
+
+    let mut stmt = conn.prepare("SELECT id, commit_sha FROM doc_debt")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    drop(stmt);
+
+    let mut dropped = 0;
+    for (id, commit_sha) in rows {
+        if commit_exists(project_root, &commit_sha) != Some(false) {
+            continue;
+        }
+        dropped += 1;
+        if !dry_run {
+            conn.execute("DELETE FROM doc_debt WHERE id = ?1", [&id])?;
+        }
+    }
+
+    Ok(dropped)
+}
+
+/// Check whether `commit_sha` still resolves in `project_root`'s git
+/// history. `Some(true)`/`Some(false)` is git's positive answer;
+/// `None` means the command itself couldn't be run (git missing,
+/// `project_root` not a repo, ...), which callers must not treat as
+/// "commit is gone".
+fn commit_exists(project_root: &Path, commit_sha: &str) -> Option<bool> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(project_root)
+        .args(["cat-file", "-e", &format!("{commit_sha}^{{commit}}")])
+        .output()
+        .ok()?;
+    Some(output.status.success())
+}
+
+// === Monorepo Doc-Debt Routing ===
+
+/// Longest-prefix router over a monorepo's configured subproject roots.
+///
+/// Attributes each changed file to the deepest (longest) matching
+/// `SubprojectConfig::root`, falling back to a synthetic `"root"` group for
+/// files that match no configured subproject.
+pub struct ChangeRouter {
+    // Sorted longest-root-first so the first match is the deepest prefix.
+    groups: Vec<SubprojectConfig>,
+}
+
+/// Name of the fallback group for files matching no configured subproject.
+pub const ROOT_GROUP: &str = "root";
+
+impl ChangeRouter {
+    /// Build a router from the project's configured subprojects.
+    pub fn new(mut groups: Vec<SubprojectConfig>) -> Self {
+        groups.sort_by(|a, b| b.root.len().cmp(&a.root.len()));
+        Self { groups }
+    }
+
+    /// Find the subproject owning `file_path` (relative to the repo root).
+    pub fn route(&self, file_path: &str) -> &str {
+        self.groups
+            .iter()
+            .find(|g| !g.root.is_empty() && path_under(file_path, &g.root))
+            .map(|g| g.name.as_str())
+            .unwrap_or(ROOT_GROUP)
+    }
+
+    /// Get the doc settings for a routed group name, if it's a configured
+    /// subproject (as opposed to the `ROOT_GROUP` fallback).
+    pub fn docs_for(&self, group: &str) -> Option<&DocsConfig> {
+        self.groups
+            .iter()
+            .find(|g| g.name == group)
+            .map(|g| &g.docs)
+    }
+
+    /// Partition changed files into `group name -> files`, deepest prefix
+    /// wins for files under nested subproject roots.
+    pub fn route_changes(&self, files: &[String]) -> BTreeMap<String, Vec<String>> {
+        let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for file in files {
+            grouped
+                .entry(self.route(file).to_string())
+                .or_default()
+                .push(file.clone());
+        }
+        grouped
+    }
+}
+
+/// Whether `file_path` is `root` itself or falls under it as a path
+/// component, not just a string prefix — plain `starts_with` would let a
+/// root of `services/auth` wrongly capture `services/auth-admin/x.rs`.
+fn path_under(file_path: &str, root: &str) -> bool {
+    let root = root.trim_end_matches('/');
+    file_path == root || file_path.starts_with(&format!("{root}/"))
+}
+
+/// Check whether a changed file looks like documentation for `docs`, by
+/// extension and include path.
+fn is_doc_change(file: &str, docs: &DocsConfig) -> bool {
+    let has_doc_extension = docs
+        .extensions
+        .iter()
+        .any(|ext| file.ends_with(&format!(".{ext}")));
+    let under_include_path = docs.include_paths.iter().any(|p| path_under(file, p));
+    has_doc_extension && under_include_path
+}
+
+/// Groups whose code changed but whose docs did not, per `docs` ownership.
+/// Renames should already be counted against their destination path by the
+/// caller (i.e. pass the post-rename path in `changed_files`).
+pub fn groups_with_doc_debt(router: &ChangeRouter, changed_files: &[String]) -> Vec<String> {
+    let routed = router.route_changes(changed_files);
+
+    let mut debt_groups = Vec::new();
+    for (group, files) in &routed {
+        let Some(docs) = router.docs_for(group) else {
+            continue; // ROOT_GROUP has no configured doc ownership to check.
+        };
+
+        let code_changed = files.iter().any(|f| !is_doc_change(f, docs));
+        let docs_changed = files.iter().any(|f| is_doc_change(f, docs));
+
+        if code_changed && !docs_changed {
+            debt_groups.push(group.clone());
+        }
+    }
+    debt_groups
+}
+
+// === User API Config (SCHEMA-003) ===
+
+/// The user's OpenRouter API config, stored in `~/.sqrl/api_config.yaml`.
+///
+/// When a vault is configured (see [`vault::Vault`]), `openrouter_api_key`
+/// is sealed at rest and only ever held in plaintext in memory; otherwise
+/// it's stored as plaintext, same as before the vault existed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserApiConfig {
+    pub openrouter_api_key: Option<String>,
+    pub model: Option<String>,
+}
+
+/// On-disk representation: the key is either plaintext or sealed, never
+/// both, so callers can't accidentally read stale plaintext after sealing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredApiConfig {
+    #[serde(default)]
+    openrouter_api_key: Option<String>,
+    #[serde(default)]
+    sealed_api_key: Option<vault::SealedSecret>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// Associated data binding a sealed API key to this config file, so it
+/// can't be copied into another user's config and opened there.
+const API_KEY_AAD: &[u8] = b"user_api_config:openrouter_api_key";
+
+fn user_api_config_path() -> Result<PathBuf, Error> {
+    let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+    Ok(home.join(".sqrl").join("api_config.yaml"))
+}
+
+/// Load the user's API config, transparently unsealing `openrouter_api_key`
+/// when a vault is configured and unlockable. If the vault is configured
+/// but can't be unlocked (e.g. no passphrase available), the key comes
+/// back as `None` rather than erroring, since most callers only need
+/// `has_api_key`-style presence checks.
+pub fn get_user_api_config() -> Result<UserApiConfig, Error> {
+    let path = user_api_config_path()?;
+    if !path.exists() {
+        return Ok(UserApiConfig::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let stored: StoredApiConfig =
+        serde_yaml::from_str(&content).map_err(|e| Error::ConfigParse(e.to_string()))?;
+
+    let openrouter_api_key = match stored.sealed_api_key {
+        Some(sealed) => Vault::unlock(None)
+            .and_then(|vault| vault.open(&sealed, API_KEY_AAD))
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok()),
+        None => stored.openrouter_api_key,
+    };
+
+    Ok(UserApiConfig {
+        openrouter_api_key,
+        model: stored.model,
+    })
+}
+
+/// Save the user's API config, sealing `openrouter_api_key` when a vault is
+/// configured and unlockable, falling back to plaintext otherwise (same as
+/// before the vault existed).
+pub fn save_user_api_config(config: &UserApiConfig) -> Result<(), Error> {
+    let path = user_api_config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let stored = match (&config.openrouter_api_key, Vault::unlock(None)) {
+        (Some(key), Ok(vault)) => StoredApiConfig {
+            openrouter_api_key: None,
+            sealed_api_key: Some(vault.seal(key.as_bytes(), API_KEY_AAD)?),
+            model: config.model.clone(),
+        },
+        _ => StoredApiConfig {
+            openrouter_api_key: config.openrouter_api_key.clone(),
+            sealed_api_key: None,
+            model: config.model.clone(),
+        },
+    };
+
+    let content = serde_yaml::to_string(&stored).map_err(|e| Error::ConfigParse(e.to_string()))?;
+    fs::write(&path, content)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,6 +1140,74 @@ mod tests {
         assert_eq!(memories[0].use_count, 2);
     }
 
+    #[test]
+    fn test_search_memories_ranks_by_relevance() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+
+        store_memory(dir.path(), "preference", "No emojis in commit messages", &[]).unwrap();
+        store_memory(
+            dir.path(),
+            "project",
+            "This project uses emojis nowhere, not in logs, not in docs, not in code comments",
+            &[],
+        )
+        .unwrap();
+        store_memory(dir.path(), "preference", "Always write tests first", &[]).unwrap();
+
+        let results = search_memories(dir.path(), "emojis", None, None).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].content, "No emojis in commit messages");
+
+        let empty = search_memories(dir.path(), "   ", None, None).unwrap();
+        assert_eq!(empty.len(), 3);
+
+        let none = search_memories(dir.path(), "nonexistentterm", None, None).unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_open_tuned_sets_wal_and_busy_timeout() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+        let path = sqrl_dir.join("memory.db");
+
+        let conn = open_tuned(&path).unwrap();
+
+        let journal_mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode.to_lowercase(), "wal");
+
+        let busy_timeout: i64 = conn
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(busy_timeout, StorageConfig::default().busy_timeout_ms as i64);
+    }
+
+    #[test]
+    fn test_run_migrations_is_idempotent_and_bumps_user_version() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+        let path = sqrl_dir.join("memory.db");
+
+        // First open runs every migration.
+        let conn = open_tuned(&path).unwrap();
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+        drop(conn);
+
+        // Re-opening shouldn't error or re-run anything (CREATE TABLE IF NOT
+        // EXISTS would be harmless either way, but user_version should stay
+        // put rather than advancing further).
+        let conn = open_tuned(&path).unwrap();
+        let version_again: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version_again, version);
+    }
+
     #[test]
     fn test_get_memories_empty() {
         let dir = tempdir().unwrap();
@@ -452,6 +1216,24 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_get_memories_since_returns_only_newer_seqs() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+
+        store_memory(dir.path(), "preference", "No emojis", &[]).unwrap();
+        let watermark = max_seq(dir.path()).unwrap();
+        store_memory(dir.path(), "preference", "Use tabs", &[]).unwrap();
+
+        let since = get_memories_since(dir.path(), watermark).unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].content, "Use tabs");
+        assert!(since[0].seq > watermark);
+
+        assert!(get_memories_since(dir.path(), max_seq(dir.path()).unwrap()).is_empty());
+    }
+
     #[test]
     fn test_format_memories_empty() {
         let dir = tempdir().unwrap();
@@ -459,4 +1241,105 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "No memories found.");
     }
+
+    fn test_router() -> ChangeRouter {
+        ChangeRouter::new(vec![
+            SubprojectConfig {
+                name: "auth".to_string(),
+                root: "services/auth/".to_string(),
+                docs: DocsConfig {
+                    extensions: vec!["md".to_string()],
+                    include_paths: vec!["services/auth/docs/".to_string()],
+                    exclude_paths: vec![],
+                },
+            },
+            SubprojectConfig {
+                name: "auth-admin".to_string(),
+                root: "services/auth/admin/".to_string(),
+                docs: DocsConfig {
+                    extensions: vec!["md".to_string()],
+                    include_paths: vec!["services/auth/admin/docs/".to_string()],
+                    exclude_paths: vec![],
+                },
+            },
+        ])
+    }
+
+    #[test]
+    fn test_route_picks_deepest_prefix() {
+        let router = test_router();
+        assert_eq!(router.route("services/auth/main.rs"), "auth");
+        assert_eq!(router.route("services/auth/admin/main.rs"), "auth-admin");
+        assert_eq!(router.route("README.md"), ROOT_GROUP);
+    }
+
+    #[test]
+    fn test_route_does_not_match_sibling_directory_sharing_a_prefix() {
+        let router = ChangeRouter::new(vec![SubprojectConfig {
+            name: "auth".to_string(),
+            root: "services/auth".to_string(),
+            docs: DocsConfig {
+                extensions: vec!["md".to_string()],
+                include_paths: vec!["docs".to_string()],
+                exclude_paths: vec![],
+            },
+        }]);
+
+        assert_eq!(router.route("services/auth/main.rs"), "auth");
+        assert_eq!(router.route("services/auth-admin/main.rs"), ROOT_GROUP);
+        assert!(!is_doc_change(
+            "docs-internal/notes.md",
+            &router.docs_for("auth").unwrap()
+        ));
+        assert!(is_doc_change(
+            "docs/notes.md",
+            &router.docs_for("auth").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_groups_with_doc_debt_flags_code_only_changes() {
+        let router = test_router();
+        let changed = vec![
+            "services/auth/main.rs".to_string(),
+            "services/auth/admin/main.rs".to_string(),
+            "services/auth/admin/docs/admin.md".to_string(),
+        ];
+
+        let debt = groups_with_doc_debt(&router, &changed);
+        assert_eq!(debt, vec!["auth".to_string()]);
+    }
+
+    #[test]
+    fn test_repair_merges_duplicates_inserted_directly() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+
+        // Bypass store_memory's dedup to simulate rows that slipped past it.
+        let (id, _, _) = store_memory(dir.path(), "preference", "No emojis", &[]).unwrap();
+        {
+            let conn = open_tuned(&db_path(dir.path())).unwrap();
+            conn.execute(
+                "INSERT INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at)
+                 VALUES ('dup-1', 'preference', 'No emojis', '[]', 3, '2020-01-01T00:00:00Z', '2020-01-01T00:00:00Z')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let dry = repair(dir.path(), true).unwrap();
+        assert!(dry.dry_run);
+        assert_eq!(dry.duplicates_merged, 1);
+        assert_eq!(get_memories(dir.path(), None, None, None).unwrap().len(), 2);
+
+        let report = repair(dir.path(), false).unwrap();
+        assert_eq!(report.duplicates_merged, 1);
+        assert!(report.integrity_ok);
+
+        let memories = get_memories(dir.path(), None, None, None).unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].id, id);
+        assert_eq!(memories[0].use_count, 4);
+    }
 }
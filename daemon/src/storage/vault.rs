@@ -0,0 +1,148 @@
+//! Optional encrypted-at-rest vault for the OpenRouter API key and memories
+//! marked `sensitive`.
+//!
+//! A passphrase (or an OS-keychain-provided secret, once one is wired in —
+//! today it's read from `SQRL_VAULT_PASSPHRASE`) is stretched into a key
+//! with Argon2id, then used to seal/open records with XChaCha20-Poly1305: a
+//! random 24-byte nonce per record, authenticated with the record's id as
+//! associated data so a ciphertext can't be silently swapped onto a
+//! different row. Without a configured vault, callers fall back to storing
+//! plaintext, same as before this existed.
+
+use std::fs;
+use std::path::PathBuf;
+
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Env var holding the vault passphrase. Stands in for an OS-keychain
+/// lookup until one is wired in.
+const PASSPHRASE_ENV_VAR: &str = "SQRL_VAULT_PASSPHRASE";
+
+/// On-disk vault config: just the Argon2id salt. The derived key itself
+/// never touches disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultConfig {
+    /// Base64-encoded random salt, generated once when the vault is first
+    /// configured.
+    salt: String,
+}
+
+/// A sealed (encrypted) record: nonce plus ciphertext, both stored
+/// base64-encoded so they fit in a TEXT column alongside plaintext rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedSecret {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// An unlocked vault, holding the derived AEAD key in memory only.
+pub struct Vault {
+    cipher: XChaCha20Poly1305,
+}
+
+impl Vault {
+    /// Path to the vault config file.
+    fn config_path() -> Result<PathBuf, Error> {
+        let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+        Ok(home.join(".sqrl").join("vault.yaml"))
+    }
+
+    /// Whether a vault has been configured on this machine.
+    pub fn is_configured() -> Result<bool, Error> {
+        Ok(Self::config_path()?.exists())
+    }
+
+    /// Initialize a new vault with a fresh random salt. Overwrites any
+    /// existing vault config (old ciphertexts become unrecoverable).
+    pub fn init() -> Result<(), Error> {
+        let path = Self::config_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut salt = [0u8; 16];
+        chacha20poly1305::aead::rand_core::RngCore::fill_bytes(&mut OsRng, &mut salt);
+        let config = VaultConfig {
+            salt: BASE64.encode(salt),
+        };
+        let content =
+            serde_yaml::to_string(&config).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Unlock the configured vault using `passphrase`, or the
+    /// `SQRL_VAULT_PASSPHRASE` env var if `passphrase` is `None`.
+    pub fn unlock(passphrase: Option<&str>) -> Result<Self, Error> {
+        let path = Self::config_path()?;
+        let content = fs::read_to_string(&path)?;
+        let config: VaultConfig =
+            serde_yaml::from_str(&content).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        let salt = BASE64
+            .decode(&config.salt)
+            .map_err(|e| Error::ConfigParse(format!("invalid vault salt: {e}")))?;
+
+        let passphrase = match passphrase {
+            Some(p) => p.to_string(),
+            None => std::env::var(PASSPHRASE_ENV_VAR)
+                .map_err(|_| Error::ConfigParse("no vault passphrase provided".to_string()))?,
+        };
+
+        let mut key_bytes = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key_bytes)
+            .map_err(|e| Error::ConfigParse(format!("key derivation failed: {e}")))?;
+
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        Ok(Self { cipher })
+    }
+
+    /// Seal `plaintext`, authenticating it with `associated_data` (e.g. the
+    /// owning record's id) so it can't be reattached to a different record.
+    pub fn seal(&self, plaintext: &[u8], associated_data: &[u8]) -> Result<SealedSecret, Error> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                chacha20poly1305::aead::Payload {
+                    msg: plaintext,
+                    aad: associated_data,
+                },
+            )
+            .map_err(|e| Error::ConfigParse(format!("encryption failed: {e}")))?;
+
+        Ok(SealedSecret {
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    /// Open a record sealed with `seal`, verifying it against the same
+    /// `associated_data` used to seal it.
+    pub fn open(&self, sealed: &SealedSecret, associated_data: &[u8]) -> Result<Vec<u8>, Error> {
+        let nonce_bytes = BASE64
+            .decode(&sealed.nonce)
+            .map_err(|e| Error::ConfigParse(format!("invalid nonce: {e}")))?;
+        let ciphertext = BASE64
+            .decode(&sealed.ciphertext)
+            .map_err(|e| Error::ConfigParse(format!("invalid ciphertext: {e}")))?;
+        let nonce = XNonce::from_slice(&nonce_bytes);
+
+        self.cipher
+            .decrypt(
+                nonce,
+                chacha20poly1305::aead::Payload {
+                    msg: &ciphertext,
+                    aad: associated_data,
+                },
+            )
+            .map_err(|_| Error::ConfigParse("failed to decrypt: wrong passphrase or tampered record".to_string()))
+    }
+}
@@ -1,17 +1,44 @@
 //! File watcher for Claude Code logs.
 
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
-use std::sync::mpsc;
-use std::time::Duration;
+use std::process::Command;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 use notify::{Config, EventKind, PollWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use serde_json::json;
 use tracing::{debug, info, warn};
 
+use crate::config::DocsConfig;
+use crate::fsignore::IgnoreSet;
 use crate::Error;
 
 /// Poll interval for watching file changes (WSL/9p filesystems don't support inotify).
 const POLL_INTERVAL_SECS: u64 = 2;
 
+/// Subscription name used when talking to Watchman.
+const WATCHMAN_SUBSCRIPTION: &str = "sqrl-jsonl";
+
+/// Default quiet period for `*_debounced` reads: how long a path must go
+/// without a new event before its coalesced event is released.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Upper bound on how long a path's event can be held back even under
+/// continuous activity, so a file that's appended to nonstop still gets
+/// processed periodically instead of never.
+const MAX_DEBOUNCE_HOLD: Duration = Duration::from_secs(2);
+
+/// A coalesced event still waiting out its quiet period.
+struct PendingEvent {
+    event: WatchEvent,
+    first_seen: Instant,
+    last_seen: Instant,
+}
+
 /// Events emitted by the file watcher.
 #[derive(Debug, Clone)]
 pub enum WatchEvent {
@@ -21,19 +48,38 @@ pub enum WatchEvent {
     Created(PathBuf),
 }
 
+/// Which underlying mechanism is driving a `FileWatcher`.
+enum Backend {
+    /// `notify::PollWatcher`, for WSL/9p filesystems where inotify doesn't
+    /// work and Watchman isn't available.
+    Poll(PollWatcher),
+    /// A Watchman subscription, already running on a background thread.
+    /// Nothing left to do in `start()` beyond what `new()` already kicked
+    /// off.
+    Watchman,
+}
+
 /// Watches ~/.claude/projects for log file changes.
-/// Uses poll-based watching for compatibility with WSL/9p filesystems.
+///
+/// Prefers a Watchman subscription (low-latency, low-CPU even on large or
+/// networked trees) when the `watchman` binary is on `PATH`, falling back
+/// to poll-based watching otherwise — the only option that's reliably
+/// correct on WSL/9p filesystems where inotify doesn't work.
 pub struct FileWatcher {
-    watcher: PollWatcher,
+    backend: Backend,
     rx: mpsc::Receiver<WatchEvent>,
     claude_dir: PathBuf,
+    /// Quiet period for `try_recv_debounced`/`recv_debounced`, configurable
+    /// via `with_debounce`.
+    debounce: Duration,
+    /// Events merged by path, waiting out their quiet period.
+    pending: HashMap<PathBuf, PendingEvent>,
+    /// Events that have cleared debouncing and are ready to hand out.
+    ready: VecDeque<WatchEvent>,
 }
 
 impl FileWatcher {
     /// Create a new file watcher.
-    ///
-    /// Uses poll-based watching for compatibility with WSL/9p filesystems
-    /// where inotify doesn't work.
     pub fn new() -> Result<Self, Error> {
         let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
         let claude_dir = home.join(".claude").join("projects");
@@ -42,8 +88,32 @@ impl FileWatcher {
             info!(path = %claude_dir.display(), "Claude projects directory does not exist yet");
         }
 
+        // Respects `~/.claude/projects/.gitignore`/`.sqrlignore` if the
+        // user adds one, plus the same default exclude paths doc-file
+        // discovery uses (node_modules/, target/, .git/, ...), so events
+        // for files under generated/vendored directories are dropped
+        // either way (the backends still see those directories).
+        let ignore = Arc::new(IgnoreSet::load(&claude_dir, &DocsConfig::default()));
+
         let (tx, rx) = mpsc::channel();
 
+        match watchman::start(&claude_dir, ignore.clone(), tx.clone()) {
+            Ok(()) => {
+                info!("Using Watchman backend for file watching");
+                return Ok(Self {
+                    backend: Backend::Watchman,
+                    rx,
+                    claude_dir,
+                    debounce: DEFAULT_DEBOUNCE,
+                    pending: HashMap::new(),
+                    ready: VecDeque::new(),
+                });
+            }
+            Err(e) => {
+                debug!(error = %e, "Watchman unavailable, falling back to poll watcher");
+            }
+        }
+
         // Use PollWatcher for WSL/9p filesystem compatibility
         // inotify doesn't work on 9p mounted filesystems (Windows drives in WSL)
         let config = Config::default().with_poll_interval(Duration::from_secs(POLL_INTERVAL_SECS));
@@ -53,8 +123,10 @@ impl FileWatcher {
                 match res {
                     Ok(event) => {
                         for path in event.paths {
-                            // Only process .jsonl files
-                            if path.extension().is_some_and(|ext| ext == "jsonl") {
+                            // Only process .jsonl files outside ignored paths
+                            if path.extension().is_some_and(|ext| ext == "jsonl")
+                                && !ignore.is_ignored(&path, false)
+                            {
                                 let watch_event = match event.kind {
                                     EventKind::Create(_) => Some(WatchEvent::Created(path.clone())),
                                     EventKind::Modify(_) => {
@@ -82,23 +154,38 @@ impl FileWatcher {
         )?;
 
         Ok(Self {
-            watcher,
+            backend: Backend::Poll(watcher),
             rx,
             claude_dir,
+            debounce: DEFAULT_DEBOUNCE,
+            pending: HashMap::new(),
+            ready: VecDeque::new(),
         })
     }
 
+    /// Override the debounce quiet period used by `try_recv_debounced`/
+    /// `recv_debounced` (default 250ms).
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
     /// Start watching for file changes.
     pub fn start(&mut self) -> Result<(), Error> {
+        let Backend::Poll(watcher) = &mut self.backend else {
+            // Watchman's subscription is already running; `new()` started
+            // it against `claude_dir` (or its parent) directly.
+            return Ok(());
+        };
+
         if self.claude_dir.exists() {
-            self.watcher
-                .watch(&self.claude_dir, RecursiveMode::Recursive)?;
+            watcher.watch(&self.claude_dir, RecursiveMode::Recursive)?;
             info!(path = %self.claude_dir.display(), "Watching for log changes");
         } else {
             // Watch parent directory so we catch when .claude/projects is created
             let parent = self.claude_dir.parent().unwrap_or(&self.claude_dir);
             if parent.exists() {
-                self.watcher.watch(parent, RecursiveMode::Recursive)?;
+                watcher.watch(parent, RecursiveMode::Recursive)?;
                 info!(path = %parent.display(), "Watching parent for .claude creation");
             } else {
                 warn!("Claude directory parent does not exist, will retry on next event");
@@ -113,14 +200,300 @@ impl FileWatcher {
         self.rx.recv().ok()
     }
 
-    /// Try to receive a watch event without blocking.
+    /// Try to receive a raw (non-debounced) watch event without blocking.
+    #[allow(dead_code)]
     pub fn try_recv(&self) -> Option<WatchEvent> {
         self.rx.try_recv().ok()
     }
 
+    /// Try to receive a debounced, coalesced watch event without blocking.
+    ///
+    /// Bursts of repeat events for the same path (poll-watcher re-scans,
+    /// Claude appending to a `.jsonl` log) are merged into one event per
+    /// path and only handed out once `debounce` has passed with no further
+    /// activity on that path, or `MAX_DEBOUNCE_HOLD` is reached under
+    /// continuous activity. A `Created` merged with a later `Modified`
+    /// stays `Created`.
+    pub fn try_recv_debounced(&mut self) -> Option<WatchEvent> {
+        self.drain_into_pending();
+        if self.ready.is_empty() {
+            self.flush_ready();
+        }
+        self.ready.pop_front()
+    }
+
+    /// Block (via short sleeps) until a debounced event is ready, or the
+    /// sending half of the channel is gone and nothing remains pending.
+    #[allow(dead_code)]
+    pub fn recv_debounced(&mut self) -> Option<WatchEvent> {
+        let poll_interval = Duration::from_millis(20).min(self.debounce);
+        loop {
+            if let Some(event) = self.try_recv_debounced() {
+                return Some(event);
+            }
+            if self.pending.is_empty() {
+                // Nothing buffered — block on the channel itself so we
+                // don't spin while genuinely idle.
+                match self.rx.recv() {
+                    Ok(event) => self.merge_event(event),
+                    Err(_) => return None,
+                }
+            } else {
+                std::thread::sleep(poll_interval);
+            }
+        }
+    }
+
+    /// Drain any events currently queued on the channel into `pending`,
+    /// merging by path.
+    fn drain_into_pending(&mut self) {
+        loop {
+            match self.rx.try_recv() {
+                Ok(event) => self.merge_event(event),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+    }
+
+    /// Merge `event` into `pending`, keyed by its path. A `Created` wins
+    /// over a `Modified` for the same path, since the downstream processor
+    /// only cares that the file exists and needs (re)reading.
+    fn merge_event(&mut self, event: WatchEvent) {
+        let now = Instant::now();
+        let path = match &event {
+            WatchEvent::Created(p) | WatchEvent::Modified(p) => p.clone(),
+        };
+        let is_created = matches!(event, WatchEvent::Created(_));
+
+        self.pending
+            .entry(path.clone())
+            .and_modify(|pending| {
+                pending.last_seen = now;
+                if is_created {
+                    pending.event = WatchEvent::Created(path.clone());
+                }
+            })
+            .or_insert(PendingEvent {
+                event,
+                first_seen: now,
+                last_seen: now,
+            });
+    }
+
+    /// Move any pending events past their quiet period (or max hold) into
+    /// `ready`.
+    fn flush_ready(&mut self) {
+        let now = Instant::now();
+        let due: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| {
+                now.duration_since(p.last_seen) >= self.debounce
+                    || now.duration_since(p.first_seen) >= MAX_DEBOUNCE_HOLD
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in due {
+            if let Some(pending) = self.pending.remove(&path) {
+                self.ready.push_back(pending.event);
+            }
+        }
+    }
+
     /// Get the Claude projects directory path.
     #[allow(dead_code)]
     pub fn claude_dir(&self) -> &PathBuf {
         &self.claude_dir
     }
 }
+
+/// Watchman protocol client: discovers the daemon's socket, issues
+/// `watch-project`/`subscribe`, and forwards matching files as
+/// `WatchEvent`s on a background thread.
+mod watchman {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct SocknameResponse {
+        sockname: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct WatchProjectResponse {
+        watch: String,
+        relative_path: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct SubscriptionUpdate {
+        #[serde(default)]
+        subscription: Option<String>,
+        #[serde(default)]
+        clock: Option<String>,
+        #[serde(default)]
+        files: Vec<FileEntry>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct FileEntry {
+        name: String,
+        exists: bool,
+        #[serde(default)]
+        new: bool,
+    }
+
+    /// Start a Watchman subscription for `.jsonl` files under `root`,
+    /// forwarding events through `tx`. Returns an error (so the caller can
+    /// fall back to polling) if the `watchman` binary isn't on `PATH`, or
+    /// any step of the handshake fails.
+    pub fn start(
+        root: &std::path::Path,
+        ignore: Arc<IgnoreSet>,
+        tx: mpsc::Sender<WatchEvent>,
+    ) -> Result<(), Error> {
+        let sockname = get_sockname()?;
+        let mut stream = UnixStream::connect(&sockname)?;
+
+        let root_str = root.to_string_lossy().to_string();
+        let watch: WatchProjectResponse =
+            send_command(&mut stream, &json!(["watch-project", root_str]))?;
+        let relative_path = watch.relative_path.unwrap_or_default();
+
+        let clock_path = clock_path()?;
+        let since = std::fs::read_to_string(&clock_path).ok();
+
+        let mut expression = json!(["suffix", "jsonl"]);
+        if !relative_path.is_empty() {
+            expression = json!(["allof", ["dirname", relative_path], expression]);
+        }
+
+        let mut subscribe_args = serde_json::Map::new();
+        subscribe_args.insert("expression".to_string(), expression);
+        subscribe_args.insert(
+            "fields".to_string(),
+            json!(["name", "exists", "new"]),
+        );
+        if let Some(clock) = since {
+            subscribe_args.insert("since".to_string(), json!(clock.trim()));
+        }
+
+        let subscribe_cmd = json!([
+            "subscribe",
+            watch.watch,
+            WATCHMAN_SUBSCRIPTION,
+            subscribe_args,
+        ]);
+        write_command(&mut stream, &subscribe_cmd)?;
+
+        // The ack for `subscribe` is itself a JSON line; consume it before
+        // handing the stream off to the reader thread so we don't race on
+        // the first subscription update.
+        let _ack: serde_json::Value = read_line(&mut stream)?;
+
+        std::thread::Builder::new()
+            .name("sqrl-watchman".to_string())
+            .spawn(move || run(stream, clock_path, ignore, tx))
+            .map_err(std::io::Error::from)?;
+
+        Ok(())
+    }
+
+    /// Read subscription updates until the connection closes or the
+    /// receiver is dropped.
+    fn run(stream: UnixStream, clock_path: PathBuf, ignore: Arc<IgnoreSet>, tx: mpsc::Sender<WatchEvent>) {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => {
+                    warn!("Watchman connection closed");
+                    return;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(error = %e, "Watchman read error");
+                    return;
+                }
+            }
+
+            let Ok(update) = serde_json::from_str::<SubscriptionUpdate>(&line) else {
+                continue;
+            };
+            if update.subscription.as_deref() != Some(WATCHMAN_SUBSCRIPTION) {
+                continue;
+            }
+
+            for file in &update.files {
+                if !file.exists {
+                    continue;
+                }
+                let path = PathBuf::from(&file.name);
+                if path.extension().is_some_and(|ext| ext == "jsonl") && !ignore.is_ignored(&path, false) {
+                    let evt = if file.new {
+                        WatchEvent::Created(path)
+                    } else {
+                        WatchEvent::Modified(path)
+                    };
+                    debug!(?evt, "Watchman file event");
+                    if tx.send(evt).is_err() {
+                        warn!("Receiver dropped, stopping Watchman watcher");
+                        return;
+                    }
+                }
+            }
+
+            // Persist the clock so a restarted daemon resumes from here
+            // instead of rescanning the whole tree.
+            if let Some(clock) = &update.clock {
+                if let Some(parent) = clock_path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                let _ = std::fs::write(&clock_path, clock);
+            }
+        }
+    }
+
+    /// Ask Watchman for its Unix socket path via `watchman get-sockname`.
+    fn get_sockname() -> Result<String, Error> {
+        let output = Command::new("watchman")
+            .arg("get-sockname")
+            .output()
+            .map_err(std::io::Error::from)?;
+        if !output.status.success() {
+            return Err(std::io::Error::other("watchman get-sockname failed").into());
+        }
+        let response: SocknameResponse = serde_json::from_slice(&output.stdout)?;
+        Ok(response.sockname)
+    }
+
+    /// Where the last subscription `clock` token is persisted between runs.
+    fn clock_path() -> Result<PathBuf, Error> {
+        let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+        Ok(home.join(".sqrl").join("watchman_clock"))
+    }
+
+    fn write_command(stream: &mut UnixStream, command: &serde_json::Value) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(command)?;
+        line.push(b'\n');
+        stream.write_all(&line)?;
+        Ok(())
+    }
+
+    fn read_line<T: for<'de> Deserialize<'de>>(stream: &mut UnixStream) -> Result<T, Error> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    fn send_command<T: for<'de> Deserialize<'de>>(
+        stream: &mut UnixStream,
+        command: &serde_json::Value,
+    ) -> Result<T, Error> {
+        write_command(stream, command)?;
+        read_line(stream)
+    }
+}
@@ -4,6 +4,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Duration, Utc};
+use metrics::counter;
 use tracing::{debug, info, warn};
 
 use crate::error::Error;
@@ -72,7 +73,10 @@ fn find_log_files(project_path: &Path, max_age_days: u32) -> Result<Vec<PathBuf>
     Ok(log_files)
 }
 
-/// Process historical logs for a project.
+/// Process historical logs for a project. Every `ProcessingStats`
+/// increment below except `sessions_found` is mirrored into a `squirrel_history_*` Prometheus
+/// counter, so ingestion throughput and failure rate are visible on
+/// `/metrics` as it runs instead of only in the summary this returns.
 pub async fn process_history(
     project_path: &Path,
     max_age_days: u32,
@@ -103,6 +107,7 @@ pub async fn process_history(
             Err(e) => {
                 warn!(file = %log_file.display(), error = %e, "Failed to read log file");
                 stats.files_failed += 1;
+                counter!("squirrel_history_files_failed_total").increment(1);
                 continue;
             }
         };
@@ -116,6 +121,7 @@ pub async fn process_history(
             match parser.parse_line(line) {
                 Ok(entry) => {
                     stats.entries_parsed += 1;
+                    counter!("squirrel_history_entries_parsed_total").increment(1);
 
                     // Accumulate entry in session tracker (all flushed at end)
                     tracker.process_entry(entry);
@@ -128,6 +134,7 @@ pub async fn process_history(
         }
 
         stats.files_processed += 1;
+        counter!("squirrel_history_files_processed_total").increment(1);
     }
 
     // Flush any remaining sessions
@@ -146,6 +153,7 @@ pub async fn process_history(
             match ipc_client.process_episode(request).await {
                 Ok(_) => {
                     stats.sessions_processed += 1;
+                    counter!("squirrel_history_sessions_processed_total").increment(1);
                 }
                 Err(e) => {
                     warn!(
@@ -154,6 +162,7 @@ pub async fn process_history(
                         "Failed to process historical session"
                     );
                     stats.sessions_failed += 1;
+                    counter!("squirrel_history_sessions_failed_total").increment(1);
                 }
             }
         }
@@ -3,13 +3,16 @@
 //! Watches for changes to Claude, Cursor, and other AI tool log files.
 
 use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
 use tokio::sync::mpsc;
 
 use crate::config::Config;
 use crate::error::Error;
+use crate::storage;
 
 /// Log watcher for AI agent files.
 pub struct LogWatcher {
@@ -17,6 +20,10 @@ pub struct LogWatcher {
     watcher: RecommendedWatcher,
     rx: mpsc::Receiver<Result<Event, notify::Error>>,
     watched_paths: HashMap<PathBuf, String>, // path -> project_id
+    project_roots: HashMap<String, PathBuf>, // project_id -> project root
+    // Byte offset each tracked file has been read up to, so a change event
+    // re-reads only the newly appended tail instead of the whole file.
+    file_offsets: HashMap<PathBuf, u64>,
 }
 
 impl LogWatcher {
@@ -34,10 +41,15 @@ impl LogWatcher {
             watcher,
             rx,
             watched_paths: HashMap::new(),
+            project_roots: HashMap::new(),
+            file_offsets: HashMap::new(),
         })
     }
 
-    /// Add a project to watch.
+    /// Add a project to watch, using its directory name as the project
+    /// id. `daemon::run` uses [`add`](Self::add) instead, for both its
+    /// initial setup and the `SIGHUP` live-reload path, passing the
+    /// registry's project id.
     pub fn watch_project(&mut self, project_path: &Path) -> Result<(), Error> {
         let project_id = project_path
             .file_name()
@@ -45,6 +57,48 @@ impl LogWatcher {
             .map(|s| s.to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
+        self.watch_project_with_id(project_path, &project_id)
+    }
+
+    /// Incrementally add a newly-registered project's watch paths without
+    /// restarting the daemon (see `daemon::run`'s `SIGHUP` handler).
+    pub fn add(&mut self, project_id: &str, project_path: &Path) -> Result<(), Error> {
+        self.watch_project_with_id(project_path, project_id)
+    }
+
+    /// Stop watching a project that disappeared from the registry,
+    /// unwatching every path recorded for it in `watched_paths`.
+    pub fn remove(&mut self, project_id: &str) -> Result<(), Error> {
+        let stale: Vec<PathBuf> = self
+            .watched_paths
+            .iter()
+            .filter(|(_, id)| id.as_str() == project_id)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in &stale {
+            self.watcher
+                .unwatch(path)
+                .map_err(|e| Error::Watcher(e.to_string()))?;
+            self.watched_paths.remove(path);
+            self.file_offsets.retain(|file, _| !file.starts_with(path));
+            tracing::debug!("Unwatched {} ({})", path.display(), project_id);
+        }
+        self.project_roots.remove(project_id);
+
+        Ok(())
+    }
+
+    /// Project ids currently being watched, used by `daemon::run`'s
+    /// `SIGHUP` handler to diff against a freshly reloaded
+    /// `ProjectsRegistry`.
+    pub fn watched_project_ids(&self) -> std::collections::HashSet<String> {
+        self.watched_paths.values().cloned().collect()
+    }
+
+    fn watch_project_with_id(&mut self, project_path: &Path, project_id: &str) -> Result<(), Error> {
+        self.project_roots.insert(project_id.to_string(), project_path.to_path_buf());
+
         // Watch .claude directory if Claude is enabled
         if self.config.agents.claude {
             let claude_dir = project_path.join(".claude");
@@ -52,7 +106,7 @@ impl LogWatcher {
                 self.watcher
                     .watch(&claude_dir, RecursiveMode::Recursive)
                     .map_err(|e| Error::Watcher(e.to_string()))?;
-                self.watched_paths.insert(claude_dir, project_id.clone());
+                self.watched_paths.insert(claude_dir, project_id.to_string());
                 tracing::debug!("Watching .claude in {}", project_path.display());
             }
         }
@@ -64,7 +118,7 @@ impl LogWatcher {
                 self.watcher
                     .watch(&cursor_dir, RecursiveMode::Recursive)
                     .map_err(|e| Error::Watcher(e.to_string()))?;
-                self.watched_paths.insert(cursor_dir, project_id.clone());
+                self.watched_paths.insert(cursor_dir, project_id.to_string());
                 tracing::debug!("Watching .cursor in {}", project_path.display());
             }
         }
@@ -86,34 +140,44 @@ impl LogWatcher {
         Ok(())
     }
 
-    /// Run the watcher loop.
+    /// Run the watcher loop to completion (used where there's nothing
+    /// else a caller needs to interleave with it).
     pub async fn run(&mut self) {
-        while let Some(result) = self.rx.recv().await {
-            match result {
-                Ok(event) => {
-                    self.handle_event(event).await;
-                }
-                Err(e) => {
-                    tracing::error!("Watch error: {}", e);
-                }
+        while self.tick().await {}
+    }
+
+    /// Process exactly one pending filesystem event, or detect that the
+    /// channel has closed. Returns `false` once closed, so it can sit as
+    /// a branch inside a `tokio::select!` loop (see `daemon::run`)
+    /// alongside signal handling without giving up control for good.
+    pub async fn tick(&mut self) -> bool {
+        match self.rx.recv().await {
+            Some(Ok(event)) => {
+                self.handle_event(event).await;
+                true
             }
+            Some(Err(e)) => {
+                tracing::error!("Watch error: {}", e);
+                true
+            }
+            None => false,
         }
     }
 
-    async fn handle_event(&self, event: Event) {
+    async fn handle_event(&mut self, event: Event) {
         use notify::EventKind;
 
         match event.kind {
             EventKind::Create(_) | EventKind::Modify(_) => {
-                for path in &event.paths {
-                    self.process_file(path).await;
+                for path in event.paths.clone() {
+                    self.process_file(&path).await;
                 }
             }
             _ => {}
         }
     }
 
-    async fn process_file(&self, path: &Path) {
+    async fn process_file(&mut self, path: &Path) {
         let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
             return;
         };
@@ -126,27 +190,204 @@ impl LogWatcher {
 
         tracing::debug!("File changed: {}", path.display());
 
-        // Determine file type and parse accordingly
+        // Determine file type and parse accordingly. Claude Code session
+        // transcripts are named `<session-uuid>.jsonl` with no identifying
+        // substring, so any `.jsonl` file that isn't an MCP log falls back
+        // to being treated as a conversation transcript.
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            if file_name.contains("conversation") || file_name.contains("chat") {
-                self.parse_conversation_log(path).await;
-            } else if file_name.contains("mcp") {
+            if file_name.contains("mcp") {
                 self.parse_mcp_log(path).await;
+            } else if ext == "jsonl" || file_name.contains("conversation") || file_name.contains("chat") {
+                self.parse_conversation_log(path).await;
+            }
+        }
+    }
+
+    /// Look up the project root that owns `path`, by finding the watched
+    /// directory it falls under and resolving that directory's project id
+    /// via `project_roots`.
+    fn project_root_for(&self, path: &Path) -> Option<PathBuf> {
+        self.watched_paths
+            .iter()
+            .find(|(watched_dir, _)| path.starts_with(watched_dir))
+            .and_then(|(_, project_id)| self.project_roots.get(project_id))
+            .cloned()
+    }
+
+    /// Read the bytes appended to `path` since it was last read, tracking
+    /// the consumed offset in `file_offsets` so later events only re-read
+    /// the tail. Only consumes up to the last newline, so a JSONL record
+    /// still being written is left for the next event instead of being
+    /// parsed half-written. Returns `None` if there's nothing new yet.
+    fn read_new_lines(&mut self, path: &Path) -> Option<Vec<String>> {
+        let mut file = std::fs::File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+        let offset = *self.file_offsets.get(path).unwrap_or(&0);
+
+        if len < offset {
+            // File shrank (rotated/truncated) since we last read it; restart.
+            self.file_offsets.insert(path.to_path_buf(), 0);
+            return self.read_new_lines(path);
+        }
+        if len == offset {
+            return None;
+        }
+
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).ok()?;
+
+        let last_newline = buf.iter().rposition(|&b| b == b'\n')?;
+        let complete = &buf[..=last_newline];
+        self.file_offsets.insert(path.to_path_buf(), offset + complete.len() as u64);
+
+        Some(
+            String::from_utf8_lossy(complete)
+                .lines()
+                .map(str::to_string)
+                .filter(|line| !line.trim().is_empty())
+                .collect(),
+        )
+    }
+
+    /// Stream newly appended lines of a Claude/Cursor conversation JSONL
+    /// log, and turn user messages that correct the assistant ("no,
+    /// don't...", "actually use...", "stop doing X") into
+    /// `memory_type="correction"` memories. Storing through `store_memory`
+    /// means a recurring correction naturally raises `use_count` via its
+    /// content dedup rather than needing its own counting here.
+    async fn parse_conversation_log(&mut self, path: &Path) {
+        let Some(project_root) = self.project_root_for(path) else {
+            tracing::debug!("No project root for {}", path.display());
+            return;
+        };
+        let Some(lines) = self.read_new_lines(path) else {
+            return;
+        };
+
+        for line in lines {
+            let Some((role, text)) = extract_role_and_text(&line) else {
+                continue;
+            };
+
+            if let Some(text) = detect_correction(&role, &text) {
+                if let Err(e) = storage::store_memory(&project_root, "correction", &text, &[]) {
+                    tracing::warn!("Failed to store correction memory: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Stream newly appended lines of an MCP tool-call log, and turn tool
+    /// failures into `memory_type="error_pattern"` memories. A tool that
+    /// keeps failing the same way gets its `use_count` bumped by
+    /// `store_memory`'s content dedup instead of being stored once per
+    /// failure.
+    async fn parse_mcp_log(&mut self, path: &Path) {
+        let Some(project_root) = self.project_root_for(path) else {
+            tracing::debug!("No project root for {}", path.display());
+            return;
+        };
+        let Some(lines) = self.read_new_lines(path) else {
+            return;
+        };
+
+        for line in lines {
+            let record: McpLogRecord = match serde_json::from_str(&line) {
+                Ok(record) => record,
+                Err(_) => continue,
+            };
+
+            if let Some(text) = detect_tool_failure(&record) {
+                if let Err(e) = storage::store_memory(&project_root, "error_pattern", &text, &[]) {
+                    tracing::warn!("Failed to store error pattern memory: {}", e);
+                }
             }
         }
     }
+}
+
+/// Pull `(role, text)` out of one line of a Claude Code/Cursor transcript
+/// JSONL log. Claude Code wraps each turn as
+/// `{"type":"user"|"assistant",...,"message":{"role":...,"content":...}}`,
+/// where `content` is either a plain string or an array of content blocks
+/// (`{"type":"text","text":"..."}`, plus tool-use/tool-result blocks we
+/// don't care about here); other loggers may write `role`/`content` at
+/// the top level instead. Parsing into a generic [`serde_json::Value`]
+/// lets both shapes resolve through the same lookup instead of failing
+/// `serde_json::from_str::<ConversationRecord>` outright on every real
+/// Claude Code log line.
+fn extract_role_and_text(line: &str) -> Option<(String, String)> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let fields = value.get("message").unwrap_or(&value);
+
+    let role = fields.get("role")?.as_str()?.to_string();
+    let text = extract_text(fields.get("content")?)?;
+    Some((role, text))
+}
 
-    async fn parse_conversation_log(&self, path: &Path) {
-        // TODO: Parse conversation logs and extract events
-        // This will be implemented to detect:
-        // - User corrections
-        // - Error patterns
-        // - Success/failure outcomes
-        tracing::debug!("Would parse conversation log: {}", path.display());
+/// Flatten a transcript `content` field into plain text, whether it's a
+/// bare string or an array of content blocks.
+fn extract_text(content: &serde_json::Value) -> Option<String> {
+    match content {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(blocks) => {
+            let text = blocks
+                .iter()
+                .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if text.is_empty() {
+                None
+            } else {
+                Some(text)
+            }
+        }
+        _ => None,
     }
+}
+
+/// Substrings (checked case-insensitively) that flag a user message as
+/// correcting the assistant rather than just continuing the conversation.
+const CORRECTION_MARKERS: &[&str] = &[
+    "no, don't",
+    "don't do that",
+    "actually use",
+    "actually, use",
+    "stop doing",
+    "that's wrong",
+];
 
-    async fn parse_mcp_log(&self, path: &Path) {
-        // TODO: Parse MCP tool call logs
-        tracing::debug!("Would parse MCP log: {}", path.display());
+fn detect_correction(role: &str, content: &str) -> Option<String> {
+    if role != "user" {
+        return None;
     }
+
+    let lower = content.to_lowercase();
+    if CORRECTION_MARKERS.iter().any(|marker| lower.contains(marker)) {
+        Some(content.to_string())
+    } else {
+        None
+    }
+}
+
+/// One line of an MCP tool-call log.
+#[derive(Debug, Deserialize)]
+struct McpLogRecord {
+    tool: String,
+    status: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+fn detect_tool_failure(record: &McpLogRecord) -> Option<String> {
+    if record.status != "error" && record.status != "failure" {
+        return None;
+    }
+
+    Some(format!(
+        "Tool '{}' failed: {}",
+        record.tool,
+        record.error.as_deref().unwrap_or("unknown error")
+    ))
 }
@@ -1,21 +1,33 @@
 //! API endpoints for Squirrel web UI.
 
+use std::sync::OnceLock;
+
 use axum::{
     extract::{Path, Query},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
+use metrics::{counter, histogram};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
 
+use crate::error::Error;
 use crate::global_config::{GlobalConfig, McpConfig};
 use crate::storage::Storage;
 
 /// API response wrapper.
-#[derive(Serialize)]
+///
+/// `data`'s real type varies per endpoint (a `McpConfig`, a list of
+/// memories, `()`, ...), so it's documented to OpenAPI as a generic
+/// `object` via `value_type` rather than needing every payload type to
+/// implement `ToSchema` itself.
+#[derive(Serialize, ToSchema)]
 struct ApiResponse<T> {
     success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Object, nullable = true)]
     data: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
@@ -55,8 +67,59 @@ impl ApiResponse<()> {
     }
 }
 
+// === Metrics ===
+//
+// `metrics_exporter_prometheus` installs itself as the process-wide
+// `metrics` recorder, so every `counter!`/`histogram!` call anywhere in
+// the crate (here and in `watcher::history`) feeds the same registry;
+// `/metrics` just renders whatever it's accumulated.
+
+static METRICS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the global Prometheus recorder. Called once from `web::serve`
+/// before the router starts accepting requests.
+pub fn init_metrics() -> Result<(), Error> {
+    let handle = PrometheusBuilder::new()
+        .install_recorder()
+        .map_err(|e| Error::Metrics(e.to_string()))?;
+    let _ = METRICS_HANDLE.set(handle);
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "metrics",
+    responses((status = 200, description = "Prometheus text exposition of accumulated metrics"))
+)]
+pub async fn metrics() -> impl IntoResponse {
+    match METRICS_HANDLE.get() {
+        Some(handle) => handle.render().into_response(),
+        None => ApiResponse::error("metrics recorder not initialized").into_response(),
+    }
+}
+
+/// Record one project-memory CRUD call's outcome: a request counter,
+/// an error counter when `is_err`, and a duration histogram, all labeled
+/// by `endpoint` so `/metrics` can break down throughput and error rate
+/// per route.
+fn record_request(endpoint: &'static str, start: std::time::Instant, is_err: bool) {
+    counter!("squirrel_api_requests_total", "endpoint" => endpoint).increment(1);
+    if is_err {
+        counter!("squirrel_api_errors_total", "endpoint" => endpoint).increment(1);
+    }
+    histogram!("squirrel_api_request_duration_seconds", "endpoint" => endpoint)
+        .record(start.elapsed().as_secs_f64());
+}
+
 // === Config endpoints ===
 
+#[utoipa::path(
+    get,
+    path = "/api/config",
+    tag = "config",
+    responses((status = 200, description = "Current global configuration", body = ApiResponse))
+)]
 pub async fn get_config() -> impl IntoResponse {
     match GlobalConfig::load() {
         Ok(config) => ApiResponse::ok(config).into_response(),
@@ -64,6 +127,16 @@ pub async fn get_config() -> impl IntoResponse {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/config",
+    tag = "config",
+    request_body = GlobalConfig,
+    responses(
+        (status = 200, description = "Configuration saved", body = ApiResponse),
+        (status = 400, description = "Configuration could not be saved", body = ApiResponse)
+    )
+)]
 pub async fn update_config(Json(config): Json<GlobalConfig>) -> impl IntoResponse {
     match config.save() {
         Ok(()) => ApiResponse::ok(config).into_response(),
@@ -73,6 +146,12 @@ pub async fn update_config(Json(config): Json<GlobalConfig>) -> impl IntoRespons
 
 // === MCP endpoints ===
 
+#[utoipa::path(
+    get,
+    path = "/api/mcps",
+    tag = "mcp",
+    responses((status = 200, description = "Configured MCP servers", body = ApiResponse))
+)]
 pub async fn list_mcps() -> impl IntoResponse {
     match GlobalConfig::list_mcps() {
         Ok(mcps) => ApiResponse::ok(mcps).into_response(),
@@ -80,6 +159,16 @@ pub async fn list_mcps() -> impl IntoResponse {
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/mcps/{name}",
+    tag = "mcp",
+    params(("name" = String, Path, description = "MCP server name")),
+    responses(
+        (status = 200, description = "MCP server config", body = ApiResponse),
+        (status = 404, description = "No MCP server by that name", body = ApiResponse)
+    )
+)]
 pub async fn get_mcp(Path(name): Path<String>) -> impl IntoResponse {
     match GlobalConfig::get_mcp(&name) {
         Ok(mcp) => ApiResponse::ok(mcp).into_response(),
@@ -87,6 +176,13 @@ pub async fn get_mcp(Path(name): Path<String>) -> impl IntoResponse {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/mcps",
+    tag = "mcp",
+    request_body = McpConfig,
+    responses((status = 200, description = "MCP server created", body = ApiResponse))
+)]
 pub async fn create_mcp(Json(mcp): Json<McpConfig>) -> impl IntoResponse {
     match GlobalConfig::save_mcp(&mcp) {
         Ok(()) => ApiResponse::ok(mcp).into_response(),
@@ -94,6 +190,14 @@ pub async fn create_mcp(Json(mcp): Json<McpConfig>) -> impl IntoResponse {
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/mcps/{name}",
+    tag = "mcp",
+    params(("name" = String, Path, description = "MCP server name")),
+    request_body = McpConfig,
+    responses((status = 200, description = "MCP server updated", body = ApiResponse))
+)]
 pub async fn update_mcp(
     Path(name): Path<String>,
     Json(mut mcp): Json<McpConfig>,
@@ -105,6 +209,13 @@ pub async fn update_mcp(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/mcps/{name}",
+    tag = "mcp",
+    params(("name" = String, Path, description = "MCP server name")),
+    responses((status = 200, description = "MCP server deleted", body = ApiResponse))
+)]
 pub async fn delete_mcp(Path(name): Path<String>) -> impl IntoResponse {
     match GlobalConfig::delete_mcp(&name) {
         Ok(()) => ApiResponse::ok(()).into_response(),
@@ -114,13 +225,19 @@ pub async fn delete_mcp(Path(name): Path<String>) -> impl IntoResponse {
 
 // === Preferences endpoints (global, ~/.sqrl/memory.db) ===
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreatePreferenceRequest {
     content: String,
     #[serde(default)]
     tags: Vec<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/preferences",
+    tag = "preferences",
+    responses((status = 200, description = "All stored preferences", body = ApiResponse))
+)]
 pub async fn list_preferences() -> impl IntoResponse {
     let db_path = match GlobalConfig::memory_db_path() {
         Ok(p) => p,
@@ -152,6 +269,13 @@ pub async fn list_preferences() -> impl IntoResponse {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/preferences",
+    tag = "preferences",
+    request_body = CreatePreferenceRequest,
+    responses((status = 200, description = "Preference stored", body = ApiResponse))
+)]
 pub async fn create_preference(Json(req): Json<CreatePreferenceRequest>) -> impl IntoResponse {
     let db_path = match GlobalConfig::memory_db_path() {
         Ok(p) => p,
@@ -176,6 +300,16 @@ pub async fn create_preference(Json(req): Json<CreatePreferenceRequest>) -> impl
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/preferences/{id}",
+    tag = "preferences",
+    params(("id" = String, Path, description = "Preference memory id")),
+    responses(
+        (status = 200, description = "Preference deleted", body = ApiResponse),
+        (status = 404, description = "No preferences database yet", body = ApiResponse)
+    )
+)]
 pub async fn delete_preference(Path(id): Path<String>) -> impl IntoResponse {
     let db_path = match GlobalConfig::memory_db_path() {
         Ok(p) => p,
@@ -197,12 +331,12 @@ pub async fn delete_preference(Path(id): Path<String>) -> impl IntoResponse {
 
 // === Memory endpoints (project-specific, .sqrl/memory.db) ===
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct ProjectQuery {
     project: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateMemoryRequest {
     memory_type: String,
     content: String,
@@ -210,7 +344,7 @@ pub struct CreateMemoryRequest {
     tags: Vec<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateMemoryRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     memory_type: Option<String>,
@@ -220,95 +354,246 @@ pub struct UpdateMemoryRequest {
     tags: Option<Vec<String>>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/memories",
+    tag = "memories",
+    params(ProjectQuery),
+    responses(
+        (status = 200, description = "All memories in the project", body = ApiResponse),
+        (status = 404, description = "Project not initialized", body = ApiResponse)
+    )
+)]
 pub async fn list_memories(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let start = std::time::Instant::now();
     let project_path = std::path::PathBuf::from(&query.project);
     let db_path = project_path.join(".sqrl").join("memory.db");
 
-    if !db_path.exists() {
-        return ApiResponse::not_found("Project not initialized").into_response();
-    }
+    let (response, is_err) = if !db_path.exists() {
+        (ApiResponse::not_found("Project not initialized").into_response(), true)
+    } else {
+        match Storage::open(&db_path) {
+            Ok(storage) => match storage.list_all_memories() {
+                Ok(memories) => (ApiResponse::ok(memories).into_response(), false),
+                Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+            },
+            Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+        }
+    };
 
-    match Storage::open(&db_path) {
-        Ok(storage) => match storage.list_all_memories() {
-            Ok(memories) => ApiResponse::ok(memories).into_response(),
-            Err(e) => ApiResponse::error(e.to_string()).into_response(),
-        },
-        Err(e) => ApiResponse::error(e.to_string()).into_response(),
-    }
+    record_request("list_memories", start, is_err);
+    response
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory id"), ProjectQuery),
+    responses(
+        (status = 200, description = "The requested memory", body = ApiResponse),
+        (status = 404, description = "Project not initialized or memory not found", body = ApiResponse)
+    )
+)]
 pub async fn get_memory(
     Path(id): Path<String>,
     Query(query): Query<ProjectQuery>,
 ) -> impl IntoResponse {
+    let start = std::time::Instant::now();
     let project_path = std::path::PathBuf::from(&query.project);
     let db_path = project_path.join(".sqrl").join("memory.db");
 
-    if !db_path.exists() {
-        return ApiResponse::not_found("Project not initialized").into_response();
-    }
+    let (response, is_err) = if !db_path.exists() {
+        (ApiResponse::not_found("Project not initialized").into_response(), true)
+    } else {
+        match Storage::open(&db_path) {
+            Ok(storage) => match storage.get_memory(&id) {
+                Ok(Some(memory)) => (ApiResponse::ok(memory).into_response(), false),
+                Ok(None) => (ApiResponse::not_found("Memory not found").into_response(), true),
+                Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+            },
+            Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+        }
+    };
 
-    match Storage::open(&db_path) {
-        Ok(storage) => match storage.get_memory(&id) {
-            Ok(Some(memory)) => ApiResponse::ok(memory).into_response(),
-            Ok(None) => ApiResponse::not_found("Memory not found").into_response(),
-            Err(e) => ApiResponse::error(e.to_string()).into_response(),
-        },
-        Err(e) => ApiResponse::error(e.to_string()).into_response(),
-    }
+    record_request("get_memory", start, is_err);
+    response
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/memories",
+    tag = "memories",
+    params(ProjectQuery),
+    request_body = CreateMemoryRequest,
+    responses(
+        (status = 200, description = "Memory stored", body = ApiResponse),
+        (status = 404, description = "Project not initialized", body = ApiResponse)
+    )
+)]
 pub async fn create_memory(
     Query(query): Query<ProjectQuery>,
     Json(req): Json<CreateMemoryRequest>,
 ) -> impl IntoResponse {
+    let start = std::time::Instant::now();
     let project_path = std::path::PathBuf::from(&query.project);
     let db_path = project_path.join(".sqrl").join("memory.db");
 
-    if !db_path.exists() {
-        return ApiResponse::not_found("Project not initialized").into_response();
-    }
+    let (response, is_err) = if !db_path.exists() {
+        (ApiResponse::not_found("Project not initialized").into_response(), true)
+    } else {
+        match Storage::open(&db_path) {
+            Ok(storage) => match storage.store_memory(&req.memory_type, &req.content, &req.tags) {
+                Ok(result) => (ApiResponse::ok(result).into_response(), false),
+                Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+            },
+            Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+        }
+    };
 
-    match Storage::open(&db_path) {
-        Ok(storage) => match storage.store_memory(&req.memory_type, &req.content, &req.tags) {
-            Ok(result) => ApiResponse::ok(result).into_response(),
-            Err(e) => ApiResponse::error(e.to_string()).into_response(),
-        },
-        Err(e) => ApiResponse::error(e.to_string()).into_response(),
-    }
+    record_request("create_memory", start, is_err);
+    response
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory id"), ProjectQuery),
+    request_body = UpdateMemoryRequest,
+    responses(
+        (status = 200, description = "Memory updated", body = ApiResponse),
+        (status = 404, description = "Project not initialized", body = ApiResponse)
+    )
+)]
 pub async fn update_memory(
     Path(id): Path<String>,
     Query(query): Query<ProjectQuery>,
     Json(req): Json<UpdateMemoryRequest>,
 ) -> impl IntoResponse {
+    let start = std::time::Instant::now();
     let project_path = std::path::PathBuf::from(&query.project);
     let db_path = project_path.join(".sqrl").join("memory.db");
 
-    if !db_path.exists() {
-        return ApiResponse::not_found("Project not initialized").into_response();
-    }
-
-    match Storage::open(&db_path) {
-        Ok(storage) => {
-            match storage.update_memory(
+    let (response, is_err) = if !db_path.exists() {
+        (ApiResponse::not_found("Project not initialized").into_response(), true)
+    } else {
+        match Storage::open(&db_path) {
+            Ok(storage) => match storage.update_memory(
                 &id,
                 req.memory_type.as_deref(),
                 req.content.as_deref(),
                 req.tags.as_deref(),
             ) {
-                Ok(()) => ApiResponse::ok(()).into_response(),
-                Err(e) => ApiResponse::error(e.to_string()).into_response(),
-            }
+                Ok(()) => (ApiResponse::ok(()).into_response(), false),
+                Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+            },
+            Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
         }
-        Err(e) => ApiResponse::error(e.to_string()).into_response(),
-    }
+    };
+
+    record_request("update_memory", start, is_err);
+    response
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory id"), ProjectQuery),
+    responses(
+        (status = 200, description = "Memory deleted", body = ApiResponse),
+        (status = 404, description = "Project not initialized", body = ApiResponse)
+    )
+)]
 pub async fn delete_memory(
     Path(id): Path<String>,
     Query(query): Query<ProjectQuery>,
+) -> impl IntoResponse {
+    let start = std::time::Instant::now();
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    let (response, is_err) = if !db_path.exists() {
+        (ApiResponse::not_found("Project not initialized").into_response(), true)
+    } else {
+        match Storage::open(&db_path) {
+            Ok(storage) => match storage.delete_memory(&id) {
+                Ok(()) => (ApiResponse::ok(()).into_response(), false),
+                Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+            },
+            Err(e) => (ApiResponse::error(e.to_string()).into_response(), true),
+        }
+    };
+
+    record_request("delete_memory", start, is_err);
+    response
+}
+
+// === Batch endpoints (modeled on Garage's K2V batch API) ===
+//
+// `create_memory`/`update_memory`/`delete_memory` are one-at-a-time,
+// which is slow when the history backfill (`cli::backfill`) or a bulk
+// import has hundreds of items to write. These apply a whole array of
+// typed operations inside a single `Storage` transaction, reporting
+// each operation's own result instead of aborting the batch on the
+// first failure.
+
+/// One operation within a batch request.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOp {
+    Insert {
+        memory_type: String,
+        content: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+    Update {
+        id: String,
+        #[serde(default)]
+        memory_type: Option<String>,
+        #[serde(default)]
+        content: Option<String>,
+        #[serde(default)]
+        tags: Option<Vec<String>>,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+/// Per-operation outcome, in the same order as the request array, so a
+/// caller can tell which of its N operations failed without the whole
+/// batch being rolled back for one bad entry.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchResult {
+    Ok {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/// `POST /api/memories/batch?project=<path>` — apply `ops` against that
+/// project's `.sqrl/memory.db` in one transaction.
+#[utoipa::path(
+    post,
+    path = "/api/memories/batch",
+    tag = "memories",
+    params(ProjectQuery),
+    request_body = Vec<BatchOp>,
+    responses(
+        (status = 200, description = "Per-operation results, same order as the request", body = ApiResponse),
+        (status = 404, description = "Project not initialized", body = ApiResponse)
+    )
+)]
+pub async fn memories_batch(
+    Query(query): Query<ProjectQuery>,
+    Json(ops): Json<Vec<BatchOp>>,
 ) -> impl IntoResponse {
     let project_path = std::path::PathBuf::from(&query.project);
     let db_path = project_path.join(".sqrl").join("memory.db");
@@ -317,11 +602,254 @@ pub async fn delete_memory(
         return ApiResponse::not_found("Project not initialized").into_response();
     }
 
+    run_batch(&db_path, ops).await
+}
+
+/// `POST /api/preferences/batch` — apply `ops` against the global
+/// `~/.sqrl/memory.db`.
+#[utoipa::path(
+    post,
+    path = "/api/preferences/batch",
+    tag = "preferences",
+    request_body = Vec<BatchOp>,
+    responses((status = 200, description = "Per-operation results, same order as the request", body = ApiResponse))
+)]
+pub async fn preferences_batch(Json(ops): Json<Vec<BatchOp>>) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    if let Some(parent) = db_path.parent() {
+        if !parent.exists() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                return ApiResponse::error(e.to_string()).into_response();
+            }
+        }
+    }
+
+    run_batch(&db_path, ops).await
+}
+
+async fn run_batch(db_path: &std::path::Path, ops: Vec<BatchOp>) -> axum::response::Response {
+    match Storage::open(db_path) {
+        Ok(storage) => match storage.apply_batch(ops) {
+            Ok(results) => ApiResponse::ok(results).into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+// === Sync endpoints (atuin-style incremental push/pull, ARCH-004) ===
+//
+// Lets a user's memories follow them across machines without a full
+// dump/import each time: the client persists the `high_water_mark` a
+// pull returns and passes it back as `since` next time, and uploads
+// whatever it changed locally to `/sync/push` keyed by `id` so the
+// server can merge with last-write-wins instead of re-importing
+// everything wholesale.
+
+/// Picks the same store a memory/preference request would: a project's
+/// `.sqrl/memory.db` when `project` is set, otherwise the global
+/// `~/.sqrl/memory.db`.
+#[derive(Deserialize, ToSchema)]
+pub struct SyncQuery {
+    #[serde(default)]
+    project: Option<String>,
+}
+
+fn sync_db_path(query: &SyncQuery) -> Result<std::path::PathBuf, crate::error::Error> {
+    match &query.project {
+        Some(project) => Ok(std::path::PathBuf::from(project).join(".sqrl").join("memory.db")),
+        None => GlobalConfig::memory_db_path(),
+    }
+}
+
+/// One memory as exchanged over the sync wire — the subset of fields a
+/// remote peer needs to merge it, keyed by `id` rather than relying on
+/// content-based dedup the way `cli::import` does for a full restore.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SyncMemory {
+    id: String,
+    memory_type: String,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    updated_at: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SyncPullQuery {
+    #[serde(flatten)]
+    store: SyncQuery,
+    since: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncPullResponse {
+    memories: Vec<SyncMemory>,
+    high_water_mark: String,
+}
+
+/// `GET /sync/pull?since=<rfc3339>[&project=<path>]` — every memory
+/// whose `updated_at` is strictly greater than `since`, plus the new
+/// high-water-mark timestamp the caller should persist as its next
+/// `since` cursor.
+#[utoipa::path(
+    get,
+    path = "/sync/pull",
+    tag = "sync",
+    params(SyncPullQuery),
+    responses(
+        (status = 200, description = "Memories changed since the cursor", body = ApiResponse),
+        (status = 404, description = "Store not initialized", body = ApiResponse),
+        (status = 400, description = "Invalid 'since' timestamp", body = ApiResponse)
+    )
+)]
+pub async fn sync_pull(Query(query): Query<SyncPullQuery>) -> impl IntoResponse {
+    let db_path = match sync_db_path(&query.store) {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+    if !db_path.exists() {
+        return ApiResponse::not_found("Store not initialized").into_response();
+    }
+
+    let since = match chrono::DateTime::parse_from_rfc3339(&query.since) {
+        Ok(t) => t.with_timezone(&chrono::Utc),
+        Err(e) => {
+            return ApiResponse::error(format!("invalid 'since' timestamp: {e}")).into_response()
+        }
+    };
+
     match Storage::open(&db_path) {
-        Ok(storage) => match storage.delete_memory(&id) {
-            Ok(()) => ApiResponse::ok(()).into_response(),
+        Ok(storage) => match storage.list_since(since) {
+            Ok(memories) => {
+                let high_water_mark = memories
+                    .iter()
+                    .map(|m: &SyncMemory| m.updated_at.clone())
+                    .max()
+                    .unwrap_or_else(|| query.since.clone());
+                ApiResponse::ok(SyncPullResponse { memories, high_water_mark }).into_response()
+            }
             Err(e) => ApiResponse::error(e.to_string()).into_response(),
         },
         Err(e) => ApiResponse::error(e.to_string()).into_response(),
     }
 }
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncPushResponse {
+    applied: usize,
+    skipped: usize,
+    high_water_mark: String,
+}
+
+/// `POST /sync/push[?project=<path>]` — merge each uploaded memory,
+/// keyed by `id`: last-write-wins on `updated_at` (skip if the incoming
+/// timestamp isn't strictly newer than what's stored, otherwise upsert),
+/// mirroring `cli::import`'s reinforce-or-insert merge but by `id`
+/// instead of by content. Returns the new high-water-mark so the client
+/// can persist it as its next pull's `since` cursor.
+#[utoipa::path(
+    post,
+    path = "/sync/push",
+    tag = "sync",
+    params(SyncQuery),
+    request_body = Vec<SyncMemory>,
+    responses((status = 200, description = "Merge results and new high-water-mark", body = ApiResponse))
+)]
+pub async fn sync_push(
+    Query(query): Query<SyncQuery>,
+    Json(incoming): Json<Vec<SyncMemory>>,
+) -> impl IntoResponse {
+    let db_path = match sync_db_path(&query) {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+    if let Some(parent) = db_path.parent() {
+        if !parent.exists() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                return ApiResponse::error(e.to_string()).into_response();
+            }
+        }
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => {
+            let mut applied = 0;
+            let mut skipped = 0;
+            let mut high_water_mark: Option<String> = None;
+
+            for memory in &incoming {
+                if high_water_mark.as_deref().map(|hwm| memory.updated_at > *hwm).unwrap_or(true) {
+                    high_water_mark = Some(memory.updated_at.clone());
+                }
+
+                match storage.upsert_if_newer(memory) {
+                    Ok(true) => applied += 1,
+                    Ok(false) => skipped += 1,
+                    Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+                }
+            }
+
+            ApiResponse::ok(SyncPushResponse {
+                applied,
+                skipped,
+                high_water_mark: high_water_mark.unwrap_or_default(),
+            })
+            .into_response()
+        }
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+// === OpenAPI schema ===
+//
+// Registers every handler's `#[utoipa::path]` and every request/response
+// type's `ToSchema` so `web::serve` can expose a generated OpenAPI 3
+// document and Swagger UI instead of this file being the only source of
+// truth for the API shape.
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_config, update_config,
+        list_mcps, get_mcp, create_mcp, update_mcp, delete_mcp,
+        list_preferences, create_preference, delete_preference,
+        list_memories, get_memory, create_memory, update_memory, delete_memory,
+        memories_batch, preferences_batch,
+        sync_pull, sync_push,
+        metrics,
+        crate::web::auth::login,
+    ),
+    components(schemas(
+        ApiResponse<()>,
+        crate::global_config::GlobalConfig,
+        crate::global_config::McpConfig,
+        CreatePreferenceRequest,
+        CreateMemoryRequest,
+        UpdateMemoryRequest,
+        ProjectQuery,
+        BatchOp,
+        BatchResult,
+        SyncQuery,
+        SyncMemory,
+        SyncPullQuery,
+        SyncPullResponse,
+        SyncPushResponse,
+        crate::web::auth::LoginRequest,
+        crate::web::auth::LoginResponse,
+    )),
+    tags(
+        (name = "config", description = "Global configuration"),
+        (name = "mcp", description = "MCP server configuration"),
+        (name = "preferences", description = "Global preferences (~/.sqrl/memory.db)"),
+        (name = "memories", description = "Project memories (.sqrl/memory.db)"),
+        (name = "sync", description = "Cross-machine incremental sync"),
+        (name = "metrics", description = "Prometheus metrics exposition"),
+        (name = "auth", description = "Bearer token login")
+    )
+)]
+pub struct ApiDoc;
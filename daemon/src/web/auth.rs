@@ -0,0 +1,129 @@
+//! JWT-based auth for the web API.
+//!
+//! `POST /auth/login` checks a password configured in `GlobalConfig`
+//! and, if it matches, issues a signed bearer token; `require_auth` is
+//! an axum middleware that verifies that token on every other route.
+//! `require_auth` is a no-op until `auth.enabled` is set in the global
+//! config (and additionally skips localhost connections by default), so
+//! an existing install keeps working unauthenticated until someone opts
+//! in — the same shape `storage::vault` uses for encryption. `login`
+//! only needs `auth.password` to be set.
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Request};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::global_config::GlobalConfig;
+
+/// How long a token is valid for after `login` issues it.
+const TOKEN_TTL_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LoginResponse {
+    token: String,
+}
+
+fn unauthorized(msg: impl Into<String>) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({ "success": false, "error": msg.into() })),
+    )
+        .into_response()
+}
+
+/// `POST /auth/login` — exchange the configured password for a bearer
+/// token signed with `GlobalConfig::auth.signing_secret`.
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Signed bearer token", body = LoginResponse),
+        (status = 401, description = "No password configured, or it didn't match")
+    )
+)]
+pub async fn login(Json(req): Json<LoginRequest>) -> impl IntoResponse {
+    let config = match GlobalConfig::load() {
+        Ok(c) => c,
+        Err(e) => return unauthorized(e.to_string()),
+    };
+
+    let Some(expected) = &config.auth.password else {
+        return unauthorized("no login password configured");
+    };
+    if req.password != *expected {
+        return unauthorized("invalid password");
+    }
+
+    let claims = Claims {
+        sub: "sqrl-web".to_string(),
+        exp: (chrono::Utc::now() + chrono::Duration::seconds(TOKEN_TTL_SECS)).timestamp(),
+    };
+    let key = EncodingKey::from_secret(config.auth.signing_secret.as_bytes());
+    match encode(&Header::default(), &claims, &key) {
+        Ok(token) => Json(LoginResponse { token }).into_response(),
+        Err(e) => unauthorized(e.to_string()),
+    }
+}
+
+/// Middleware guarding every protected route: verifies `Authorization:
+/// Bearer <token>` against `GlobalConfig::auth.signing_secret`. Falls
+/// through untouched when `auth.enabled` is false, or when
+/// `auth.allow_loopback` is set and the request came from localhost —
+/// the web server requires `into_make_service_with_connect_info` for the
+/// latter check to see a real peer address.
+pub async fn require_auth(req: Request, next: Next) -> Response {
+    let config = match GlobalConfig::load() {
+        Ok(c) => c,
+        Err(e) => return unauthorized(e.to_string()),
+    };
+
+    if !config.auth.enabled {
+        return next.run(req).await;
+    }
+
+    if config.auth.allow_loopback {
+        let is_loopback = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .is_some_and(|ConnectInfo(addr)| addr.ip().is_loopback());
+        if is_loopback {
+            return next.run(req).await;
+        }
+    }
+
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    let Some(token) = token else {
+        return unauthorized("missing bearer token");
+    };
+
+    let key = DecodingKey::from_secret(config.auth.signing_secret.as_bytes());
+    match decode::<Claims>(token, &key, &Validation::default()) {
+        Ok(_) => next.run(req).await,
+        Err(e) => unauthorized(format!("invalid token: {e}")),
+    }
+}
@@ -2,12 +2,15 @@
 
 mod api;
 mod assets;
+mod auth;
 
 use std::net::SocketAddr;
 
 use axum::{routing::get, Router};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::error::Error;
 use crate::global_config::GlobalConfig;
@@ -19,8 +22,12 @@ pub async fn serve(open_browser: bool) -> Result<(), Error> {
     let config = GlobalConfig::load()?;
     let port = config.ui.port;
 
-    let app = Router::new()
-        // API routes
+    api::init_metrics()?;
+
+    // Config/MCP/preferences/memories/sync/batch all sit behind
+    // `auth::require_auth`, which is itself a no-op until `auth.enabled`
+    // is set — see `global_config::AuthConfig`.
+    let protected = Router::new()
         .route("/api/config", get(api::get_config).post(api::update_config))
         .route("/api/mcps", get(api::list_mcps).post(api::create_mcp))
         .route(
@@ -47,6 +54,22 @@ pub async fn serve(open_browser: bool) -> Result<(), Error> {
                 .put(api::update_memory)
                 .delete(api::delete_memory),
         )
+        .route("/api/memories/batch", axum::routing::post(api::memories_batch))
+        .route(
+            "/api/preferences/batch",
+            axum::routing::post(api::preferences_batch),
+        )
+        // Sync (atuin-style incremental push/pull across machines)
+        .route("/sync/pull", get(api::sync_pull))
+        .route("/sync/push", axum::routing::post(api::sync_push))
+        .layer(axum::middleware::from_fn(auth::require_auth));
+
+    let app = Router::new()
+        .route("/auth/login", axum::routing::post(auth::login))
+        .route("/metrics", get(api::metrics))
+        .merge(protected)
+        // Machine-readable API reference, generated from the handlers above
+        .merge(SwaggerUi::new("/api-docs/swagger-ui").url("/api-docs/openapi.json", api::ApiDoc::openapi()))
         // Static assets
         .fallback(assets::serve_static)
         .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any));
@@ -63,7 +86,11 @@ pub async fn serve(open_browser: bool) -> Result<(), Error> {
     }
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }